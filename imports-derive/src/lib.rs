@@ -0,0 +1,146 @@
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Field, Fields, LitStr, Type};
+
+/// Implements `crate::row::ExcelRow` for a struct with named fields.
+///
+/// Every field is read from the column `#[excel(header = "..")]`, other spellings of the header
+/// are given with `alias = ".."` or as case insensitive regex with `pattern = ".."`, both may be
+/// repeated. `Option` fields may be empty, fields marked `#[excel(skip)]` are not read from the
+/// sheet and start with their default value. `bool` fields with `yes = ".."` are flags that are
+/// set by that value and unset by a no, other values are errors, see `crate::row::flag`.
+#[proc_macro_derive(ExcelRow, attributes(excel))]
+pub fn derive_excel_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+struct Column {
+    header: Option<LitStr>,
+    aliases: Vec<LitStr>,
+    patterns: Vec<LitStr>,
+    yes: Vec<LitStr>,
+    skip: bool,
+}
+
+fn column(field: &Field) -> syn::Result<Column> {
+    let mut column = Column {
+        header: None,
+        aliases: vec![],
+        patterns: vec![],
+        yes: vec![],
+        skip: false,
+    };
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("excel")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("header") {
+                column.header = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("alias") {
+                column.aliases.push(meta.value()?.parse()?);
+            } else if meta.path.is_ident("pattern") {
+                column.patterns.push(meta.value()?.parse()?);
+            } else if meta.path.is_ident("yes") {
+                column.yes.push(meta.value()?.parse()?);
+            } else if meta.path.is_ident("skip") {
+                column.skip = true;
+            } else {
+                return Err(meta.error("expected `header`, `alias`, `pattern`, `yes` or `skip`"));
+            }
+            Ok(())
+        })?;
+    }
+
+    if column.header.is_none() && !column.skip {
+        return Err(syn::Error::new_spanned(
+            field,
+            "missing #[excel(header = \"..\")] or #[excel(skip)]",
+        ));
+    }
+    Ok(column)
+}
+
+fn is_option(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "Option"),
+        _ => false,
+    }
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input,
+                    "ExcelRow needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input,
+                "ExcelRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let mut headers = vec![];
+    let mut values = vec![];
+    for field in fields {
+        let name = &field.ident;
+        let column = column(field)?;
+        let header = match column.header {
+            Some(header) if !column.skip => header,
+            _ => {
+                values.push(quote! { #name: ::std::default::Default::default() });
+                continue;
+            }
+        };
+
+        let index = headers.len();
+        let aliases = &column.aliases;
+        let patterns = &column.patterns;
+        headers.push(quote! {
+            crate::row::Header {
+                name: #header,
+                aliases: &[#(#aliases),*],
+                patterns: &[#(#patterns),*],
+            }
+        });
+        let cell = quote! { crate::excel::cell(row, columns[#index]) };
+        let yes = &column.yes;
+        values.push(match (yes.is_empty(), is_option(&field.ty)) {
+            (true, true) => quote! { #name: crate::row::optional(row_number, #header, #cell)? },
+            (true, false) => quote! { #name: crate::row::required(row_number, #header, #cell)? },
+            (false, true) => {
+                quote! { #name: crate::row::flag(row_number, #header, #cell, &[#(#yes),*])? }
+            }
+            (false, false) => quote! {
+                #name: crate::row::flag(row_number, #header, #cell, &[#(#yes),*])?.unwrap_or(false)
+            },
+        });
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics crate::row::ExcelRow for #ident #ty_generics #where_clause {
+            const HEADERS: &'static [crate::row::Header] = &[#(#headers),*];
+
+            fn from_cells(
+                row: &[::calamine::DataType],
+                columns: &[usize],
+                row_number: usize,
+            ) -> ::std::result::Result<Self, crate::ImportError> {
+                Ok(#ident { #(#values),* })
+            }
+        }
+    })
+}
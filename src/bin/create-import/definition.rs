@@ -0,0 +1,174 @@
+use convert_case::{Case, Casing};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::path::Path;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Kind {
+    #[serde(rename = "date")]
+    Date,
+    #[serde(rename = "time")]
+    Time,
+    #[serde(rename = "datetime")]
+    DateTime,
+    #[serde(rename = "string")]
+    String,
+    #[serde(rename = "float")]
+    Float,
+    #[serde(rename = "integer")]
+    Integer,
+    /// ja/nein, X or empty, or a flag set by the `yes` values
+    #[serde(rename = "boolean")]
+    Boolean,
+    /// amounts and prices, read exactly instead of as f64
+    #[serde(rename = "decimal")]
+    Decimal,
+    #[serde(rename = "enum")]
+    Enum,
+    #[serde(rename = "meterpoint")]
+    Meterpoint,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ColumnDefinition {
+    #[serde(rename = "name")]
+    pub header_name: String,
+    /// single alias of older definitions, see `aliases`
+    #[serde(rename = "name2")]
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub header_name2: String,
+    /// other spellings of the header
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// case insensitive regular expressions matched against the header
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub patterns: Vec<String>,
+    #[serde(rename = "type")]
+    pub kind: Kind,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    #[serde(rename = "enum", skip_serializing_if = "Option::is_none")]
+    pub enum_: Option<String>,
+    /// labels of an enum column, the enum module is generated when given
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<EnumValue>,
+    /// values that set a boolean flag, like `E` in "SEPA hinterlegt E=JA"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub yes: Vec<String>,
+}
+
+/// What an enum variant is serialised as, a name or a number like a rating.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum Value {
+    Name(String),
+    Number(u8),
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EnumValue {
+    pub value: Value,
+    /// name of the variant, the value in pascal case by default
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub variant: Option<String>,
+    /// texts in the sheet, compared case insensitive
+    pub labels: Vec<String>,
+}
+
+impl EnumValue {
+    pub fn variant(&self) -> Option<String> {
+        match (&self.variant, &self.value) {
+            (Some(variant), _) => Some(variant.clone()),
+            (None, Value::Name(name)) => Some(name.to_case(Case::Pascal)),
+            (None, Value::Number(_)) => None,
+        }
+    }
+}
+
+impl ColumnDefinition {
+    /// Module and type of an enum column, `contact_type::ContactType`.
+    pub fn enum_path(&self) -> Option<(&str, &str)> {
+        let (module, name) = self.enum_.as_deref()?.rsplit_once("::")?;
+        let identifier =
+            |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if identifier(module) && identifier(name) {
+            Some((module, name))
+        } else {
+            None
+        }
+    }
+
+    /// Names and aliases a header may have.
+    pub fn names(&self) -> impl Iterator<Item = &String> {
+        std::iter::once(&self.header_name)
+            .chain(std::iter::once(&self.header_name2))
+            .chain(&self.aliases)
+            .filter(|name| !name.trim().is_empty())
+    }
+}
+
+/// Fields the rows are grouped by, `"customerId"` or `["supplierCustomerId", "invoice"]`.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GroupKey {
+    Field(String),
+    Fields(Vec<String>),
+}
+
+impl GroupKey {
+    pub fn fields(&self) -> &[String] {
+        match self {
+            GroupKey::Field(field) => std::slice::from_ref(field),
+            GroupKey::Fields(fields) => fields,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Definition {
+    /// computed fields set by the `postRow` hook may be part of the key
+    #[serde(rename = "groupKey")]
+    pub group_key: GroupKey,
+    #[serde(default)]
+    #[serde(rename = "dataStartRowNumber")]
+    pub data_start_row_number: usize,
+    #[serde(default)]
+    #[serde(rename = "headerRowNumber")]
+    pub header_row_number: usize,
+    #[serde(rename = "sheetName")]
+    pub sheet_name: Option<String>,
+    /// regex matched against the sheet names
+    #[serde(rename = "sheetPattern")]
+    pub sheet_pattern: Option<String>,
+    /// concatenate the rows of every matching sheet
+    #[serde(default)]
+    #[serde(rename = "allSheets")]
+    pub all_sheets: bool,
+    pub columns: HashMap<String, ColumnDefinition>,
+    /// fields that are not read from the sheet but set by the `postRow` hook, always optional
+    #[serde(default)]
+    pub computed: HashMap<String, Kind>,
+    /// function in src/<name>/hooks.rs that is called with every row after it was read
+    #[serde(rename = "postRow")]
+    pub post_row: Option<String>,
+    #[serde(rename = "stopWhen")]
+    pub stop_when: Option<StopWhen>,
+}
+
+/// The data ends before the first row whose cell in `column` is `equals`, e.g. a summary row.
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct StopWhen {
+    pub column: String,
+    pub equals: String,
+}
+
+impl Definition {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Definition, String> {
+        let file = File::open(&path).map_err(|e| e.to_string())?;
+        serde_json::from_reader(file).map_err(|e| e.to_string())
+    }
+}
@@ -0,0 +1,358 @@
+use crate::definition::{ColumnDefinition, Definition, EnumValue, Kind, Value};
+use crate::lint;
+use calamine::{open_workbook_auto, DataType, Range, Reader};
+use convert_case::{Case, Casing};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+
+/// Data rows that are looked at for the kind of a column.
+const SAMPLE: usize = 1000;
+/// Text columns with at most this many distinct values are suggested as enum.
+const MAX_ENUM_VALUES: usize = 8;
+
+/// Definition as it is written, with the fields in the order of the other definitions.
+#[derive(Debug, Serialize)]
+pub struct Draft {
+    #[serde(rename = "groupKey")]
+    group_key: String,
+    #[serde(rename = "headerRowNumber")]
+    header_row_number: usize,
+    #[serde(rename = "dataStartRowNumber")]
+    data_start_row_number: usize,
+    #[serde(rename = "sheetName", skip_serializing_if = "Option::is_none")]
+    sheet_name: Option<String>,
+    columns: BTreeMap<String, ColumnDefinition>,
+}
+
+fn is_empty(cell: &DataType) -> bool {
+    match cell {
+        DataType::Empty => true,
+        DataType::String(s) => s.trim().is_empty(),
+        _ => false,
+    }
+}
+
+/// The first row that only has text and fills at least half of the sheet.
+fn header_row(sheet: &Range<DataType>) -> Option<usize> {
+    let width = sheet.width();
+    sheet.rows().position(|row| {
+        let filled: Vec<&DataType> = row.iter().filter(|cell| !is_empty(cell)).collect();
+        filled.len() >= 2.max(width / 2)
+            && filled
+                .iter()
+                .all(|cell| matches!(cell, DataType::String(_)))
+    })
+}
+
+/// camelCase key of a header, `Buch.dat.` is `buchDat`.
+fn key(header: &str) -> String {
+    let words: String = header
+        .chars()
+        .flat_map(|c| match c {
+            'ä' => vec!['a', 'e'],
+            'ö' => vec!['o', 'e'],
+            'ü' => vec!['u', 'e'],
+            'Ä' => vec!['A', 'e'],
+            'Ö' => vec!['O', 'e'],
+            'Ü' => vec!['U', 'e'],
+            'ß' => vec!['s', 's'],
+            c if c.is_ascii_alphanumeric() => vec![c],
+            _ => vec![' '],
+        })
+        .collect();
+    let key = words.to_case(Case::Camel);
+    if key.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("column{}", key)
+    } else {
+        key
+    }
+}
+
+fn is_meterpoint(value: &str) -> bool {
+    let value: String = value.chars().filter(|c| !c.is_whitespace()).collect();
+    value.len() == 33 && value.to_uppercase().starts_with("AT")
+}
+
+/// Distinct labels in the order they were seen, if the texts look like a choice of values.
+fn labels(texts: &[&str]) -> Option<Vec<String>> {
+    let mut seen = HashSet::new();
+    let labels: Vec<String> = texts
+        .iter()
+        .map(|text| text.trim())
+        .filter(|text| seen.insert(text.to_lowercase()))
+        .map(str::to_string)
+        .collect();
+
+    let mut variants = HashSet::new();
+    let values_are_names = labels.iter().all(|label| {
+        let variant = key(label).to_case(Case::Pascal);
+        !variant.starts_with("Column") && variants.insert(variant)
+    });
+    let repeated = labels.len() * 2 <= texts.len();
+    (labels.len() <= MAX_ENUM_VALUES && repeated && values_are_names).then_some(labels)
+}
+
+/// Kind of a column by the cells that are not empty, enum candidates come with their labels.
+fn kind(cells: &[&DataType]) -> (Kind, Vec<String>) {
+    let german_date = Regex::new(r"^\d{1,2}\.\d{1,2}\.\d{4}$").expect("valid regex");
+    let all = |f: fn(&DataType) -> bool| !cells.is_empty() && cells.iter().all(|c| f(c));
+
+    if all(|c| matches!(c, DataType::Bool(_))) {
+        return (Kind::Boolean, vec![]);
+    }
+    if all(|c| matches!(c, DataType::DateTime(_))) {
+        let serials: Vec<f64> = cells
+            .iter()
+            .filter_map(|c| match c {
+                DataType::DateTime(f) => Some(*f),
+                _ => None,
+            })
+            .collect();
+        return if serials.iter().all(|f| f.fract() == 0.0) {
+            (Kind::Date, vec![])
+        } else if serials.iter().all(|f| *f < 1.0) {
+            (Kind::Time, vec![])
+        } else {
+            (Kind::DateTime, vec![])
+        };
+    }
+    if all(|c| matches!(c, DataType::Int(_) | DataType::Float(_))) {
+        let integral = cells
+            .iter()
+            .filter_map(|c| c.get_float())
+            .all(|f| f.fract() == 0.0);
+        return match integral {
+            true => (Kind::Integer, vec![]),
+            false => (Kind::Float, vec![]),
+        };
+    }
+
+    let texts: Vec<&str> = cells.iter().filter_map(|c| c.get_string()).collect();
+    if texts.is_empty() || texts.len() != cells.len() {
+        return (Kind::String, vec![]);
+    }
+    if texts.iter().all(|text| is_meterpoint(text)) {
+        return (Kind::Meterpoint, vec![]);
+    }
+    if texts.iter().all(|text| german_date.is_match(text.trim())) {
+        return (Kind::Date, vec![]);
+    }
+    match labels(&texts) {
+        Some(labels) => (Kind::Enum, labels),
+        None => (Kind::String, vec![]),
+    }
+}
+
+/// Draft definition of a sheet, `sheet_name` is given for workbooks with several sheets.
+pub fn infer(sheet: &Range<DataType>, sheet_name: Option<String>) -> Result<Draft, String> {
+    let header_row = header_row(sheet).ok_or("no header row found")?;
+    let rows: Vec<&[DataType]> = sheet.rows().skip(header_row).take(SAMPLE + 1).collect();
+
+    let mut columns = BTreeMap::new();
+    let mut order = vec![];
+    for (i, header) in rows[0].iter().enumerate() {
+        let name = header.to_string().trim().to_string();
+        if name.is_empty() {
+            continue;
+        }
+        let mut column_key = key(&name);
+        if column_key.is_empty() {
+            column_key = format!("column{}", i + 1);
+        }
+        let base = column_key.clone();
+        for n in 2.. {
+            if !columns.contains_key(&column_key) {
+                break;
+            }
+            column_key = format!("{}{}", base, n);
+        }
+
+        let cells: Vec<&DataType> = rows[1..]
+            .iter()
+            .map(|row| row.get(i).unwrap_or(&DataType::Empty))
+            .collect();
+        let filled: Vec<&DataType> = cells.iter().copied().filter(|c| !is_empty(c)).collect();
+        let (kind, labels) = kind(&filled);
+
+        let enum_ = (kind == Kind::Enum).then(|| {
+            format!(
+                "{}::{}",
+                column_key.to_case(Case::Snake),
+                column_key.to_case(Case::Pascal)
+            )
+        });
+        let values = labels
+            .into_iter()
+            .map(|label| EnumValue {
+                value: Value::Name(key(&label).to_case(Case::Kebab)),
+                variant: None,
+                labels: vec![label.to_lowercase()],
+            })
+            .collect();
+
+        order.push(column_key.clone());
+        columns.insert(
+            column_key,
+            ColumnDefinition {
+                header_name: name,
+                header_name2: String::new(),
+                aliases: vec![],
+                patterns: vec![],
+                optional: filled.len() < cells.len(),
+                kind,
+                enum_,
+                values,
+                yes: vec![],
+            },
+        );
+    }
+
+    let group_key = order
+        .iter()
+        .find(|key| {
+            let column: &ColumnDefinition = &columns[*key];
+            column.kind == Kind::String && !column.optional
+        })
+        .or(order.first())
+        .cloned()
+        .unwrap_or_default();
+
+    Ok(Draft {
+        group_key,
+        header_row_number: header_row + 1,
+        data_start_row_number: header_row + 2,
+        sheet_name,
+        columns,
+    })
+}
+
+/// Writes the draft definition of the first sheet of `path` to `output`, by default
+/// data/<name>.columns.json, and prints what needs a review.
+pub fn run(path: &str, output: Option<&str>) -> Result<(), String> {
+    let mut workbook = open_workbook_auto(path).map_err(|e| format!("{}: {}", path, e))?;
+    let names = workbook.sheet_names().to_vec();
+    let name = names.first().ok_or_else(|| format!("{}: no sheet", path))?;
+    let sheet = workbook
+        .worksheet_range(name)
+        .ok_or_else(|| format!("{}: no sheet {}", path, name))?
+        .map_err(|e| format!("{}: {}", path, e))?;
+    let sheet_name = (names.len() > 1).then(|| name.clone());
+    let draft = infer(&sheet, sheet_name).map_err(|e| format!("{}: {}", path, e))?;
+
+    let output = match output {
+        Some(output) => output.to_string(),
+        None => {
+            let stem = Path::new(path)
+                .file_stem()
+                .map_or(String::new(), |s| s.to_string_lossy().to_case(Case::Snake));
+            format!("data/{}.columns.json", stem)
+        }
+    };
+    if Path::new(&output).exists() {
+        return Err(format!("{}: exists already", output));
+    }
+    let json = serde_json::to_string_pretty(&draft).map_err(|e| e.to_string())?;
+    std::fs::write(&output, json + "\n").map_err(|e| format!("{}: {}", output, e))?;
+
+    eprintln!(
+        "{}: {} columns, review the kinds, optional columns and the groupKey {}",
+        output,
+        draft.columns.len(),
+        draft.group_key
+    );
+    let definition: Definition = serde_json::to_value(&draft)
+        .and_then(serde_json::from_value)
+        .map_err(|e| e.to_string())?;
+    for problem in lint::problems(&definition) {
+        eprintln!("{}: {}", output, problem);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::definition::Kind;
+    use crate::infer::{infer, key};
+    use calamine::{DataType, Range};
+
+    fn text(value: &str) -> DataType {
+        DataType::String(value.to_string())
+    }
+
+    #[test]
+    fn test_key() {
+        assert_eq!(key("Buch.dat."), "buchDat");
+        assert_eq!(key("Zählpunktbezeichnung"), "zaehlpunktbezeichnung");
+        assert_eq!(key("E-Rechnung ZPDF=ja"), "eRechnungZpdfJa");
+        assert_eq!(key("2024 Menge"), "column2024Menge");
+        assert_eq!(key("contactType"), "contactType");
+        assert_eq!(key("SEPA gesperrt"), "sepaGesperrt");
+    }
+
+    #[test]
+    fn test_infer() {
+        let rows = [
+            vec![text("Provisionsabrechnung")],
+            vec![],
+            vec![
+                text("Geschäftspartner"),
+                text("Status"),
+                text("Buch.dat."),
+                text("Uhrzeit"),
+                text("Erstellt"),
+            ],
+            vec![
+                text("1"),
+                text("Erledigt"),
+                DataType::DateTime(45291.0),
+                DataType::DateTime(0.5),
+                DataType::DateTime(45291.5),
+            ],
+            vec![
+                text("2"),
+                text("offen"),
+                DataType::Empty,
+                DataType::DateTime(0.25),
+                DataType::DateTime(45292.0),
+            ],
+            vec![
+                text("3"),
+                text("erledigt"),
+                DataType::DateTime(45292.0),
+                DataType::DateTime(0.75),
+                DataType::DateTime(45292.25),
+            ],
+            vec![
+                text("4"),
+                text("Offen"),
+                DataType::DateTime(45293.0),
+                DataType::DateTime(0.0),
+                DataType::DateTime(45293.75),
+            ],
+        ];
+        let mut sheet = Range::new((0, 0), (rows.len() as u32 - 1, 4));
+        for (r, row) in rows.into_iter().enumerate() {
+            for (c, cell) in row.into_iter().enumerate() {
+                sheet.set_value((r as u32, c as u32), cell);
+            }
+        }
+
+        let draft = infer(&sheet, None).unwrap();
+        assert_eq!(draft.header_row_number, 3);
+        assert_eq!(draft.data_start_row_number, 4);
+        assert_eq!(draft.group_key, "geschaeftspartner");
+
+        let date = &draft.columns["buchDat"];
+        assert_eq!((&date.kind, date.optional), (&Kind::Date, true));
+        assert_eq!(draft.columns["uhrzeit"].kind, Kind::Time);
+        assert_eq!(draft.columns["erstellt"].kind, Kind::DateTime);
+        let status = &draft.columns["status"];
+        assert_eq!(status.kind, Kind::Enum);
+        assert_eq!(status.enum_.as_deref(), Some("status::Status"));
+        assert_eq!(status.values.len(), 2);
+        assert_eq!(status.values[1].labels, vec!["offen"]);
+        assert_eq!(draft.columns["geschaeftspartner"].kind, Kind::String);
+    }
+}
@@ -0,0 +1,359 @@
+use crate::definition::{Definition, EnumValue, Kind, Value};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// Everything that would make the generated importer fail to compile or to read a sheet.
+pub fn problems(definition: &Definition) -> Vec<String> {
+    let mut problems = vec![];
+
+    if definition.header_row_number == 0 {
+        problems.push("headerRowNumber is missing or 0, rows are counted from 1".to_string());
+    }
+    if definition.data_start_row_number <= definition.header_row_number {
+        problems.push(format!(
+            "dataStartRowNumber {} has to be after headerRowNumber {}",
+            definition.data_start_row_number, definition.header_row_number
+        ));
+    }
+
+    let group_key = definition.group_key.fields();
+    if group_key.is_empty() {
+        problems.push("groupKey has no fields".to_string());
+    }
+    for (i, field) in group_key.iter().enumerate() {
+        if group_key[..i].contains(field) {
+            problems.push(format!("groupKey {} is given twice", field));
+        }
+        match definition.columns.get(field) {
+            None if definition.computed.contains_key(field) => {}
+            None => problems.push(format!("groupKey {} is not among the columns", field)),
+            Some(column) if column.kind == Kind::Enum => {
+                problems.push(format!("groupKey {} can't be an enum column", field))
+            }
+            Some(_) => {}
+        }
+    }
+
+    if definition.sheet_name.is_some() && definition.sheet_pattern.is_some() {
+        problems.push("only one of sheetName and sheetPattern can be given".to_string());
+    }
+    if let Some(pattern) = &definition.sheet_pattern {
+        if let Err(e) = Regex::new(pattern) {
+            problems.push(format!("sheetPattern {} is invalid: {}", pattern, e));
+        }
+    }
+
+    let mut computed: Vec<(&String, &Kind)> = definition.computed.iter().collect();
+    computed.sort_by_key(|(key, _)| *key);
+    for (key, kind) in computed {
+        if definition.columns.contains_key(key) {
+            problems.push(format!("computed {} is also a column", key));
+        }
+        if !is_identifier(key) {
+            problems.push(format!("computed {} is no identifier", key));
+        }
+        if *kind == Kind::Enum {
+            problems.push(format!("computed {} can't be an enum", key));
+        }
+    }
+    if !definition.computed.is_empty() && definition.post_row.is_none() {
+        problems.push("computed fields need a postRow hook that sets them".to_string());
+    }
+    if let Some(hook) = &definition.post_row {
+        if !is_identifier(hook) {
+            problems.push(format!("postRow {} is no function name", hook));
+        }
+    }
+    if let Some(stop_when) = &definition.stop_when {
+        if !definition.columns.contains_key(&stop_when.column) {
+            problems.push(format!(
+                "stopWhen column {} is not among the columns",
+                stop_when.column
+            ));
+        }
+    }
+
+    let mut keys: Vec<&String> = definition.columns.keys().collect();
+    keys.sort();
+
+    let mut claimed: HashMap<String, &str> = HashMap::new();
+    for key in keys {
+        let column = &definition.columns[key];
+        if column.header_name.trim().is_empty() {
+            problems.push(format!("column {}: name is empty", key));
+        }
+
+        match (&column.kind, &column.enum_) {
+            (Kind::Enum, None) => problems.push(format!(
+                "column {}: type enum needs the path of the enum, e.g. \"enum\": \"status::Status\"",
+                key
+            )),
+            (Kind::Enum, Some(path)) if column.enum_path().is_none() => problems.push(format!(
+                "column {}: enum {} is no path like status::Status",
+                key, path
+            )),
+            (Kind::Enum, Some(_)) => {}
+            (_, Some(_)) => problems.push(format!(
+                "column {}: enum is only used with type enum",
+                key
+            )),
+            (_, None) => {}
+        }
+
+        if column.kind != Kind::Enum && !column.values.is_empty() {
+            problems.push(format!(
+                "column {}: values are only used with type enum",
+                key
+            ));
+        }
+        if column.kind != Kind::Boolean && !column.yes.is_empty() {
+            problems.push(format!(
+                "column {}: yes is only used with type boolean",
+                key
+            ));
+        }
+        if column.yes.iter().any(|yes| yes.trim().is_empty()) {
+            problems.push(format!("column {}: yes has an empty value", key));
+        }
+        problems.extend(
+            value_problems(&column.values)
+                .into_iter()
+                .map(|problem| format!("column {}: {}", key, problem)),
+        );
+
+        for pattern in &column.patterns {
+            if let Err(e) = Regex::new(pattern) {
+                problems.push(format!(
+                    "column {}: pattern {} is invalid: {}",
+                    key, pattern, e
+                ));
+            }
+        }
+
+        for name in column.names() {
+            let name = name.trim().to_lowercase();
+            match claimed.insert(name.clone(), key) {
+                Some(other) if other != key => problems.push(format!(
+                    "header {} is claimed by the columns {} and {}",
+                    name, other, key
+                )),
+                _ => {}
+            }
+        }
+    }
+
+    problems
+}
+
+fn value_problems(values: &[EnumValue]) -> Vec<String> {
+    let mut problems = vec![];
+    let numbers = values
+        .iter()
+        .filter(|v| matches!(v.value, Value::Number(_)))
+        .count();
+    if numbers != 0 && numbers != values.len() {
+        problems.push("values are either all names or all numbers".to_string());
+    }
+
+    let mut variants = HashSet::new();
+    let mut serialised = HashSet::new();
+    let mut labels: HashMap<String, String> = HashMap::new();
+    for value in values {
+        let shown = match &value.value {
+            Value::Name(name) => name.clone(),
+            Value::Number(number) => number.to_string(),
+        };
+        if !serialised.insert(shown.clone()) {
+            problems.push(format!("value {} is given twice", shown));
+        }
+        match value.variant() {
+            None => problems.push(format!("value {} needs a variant", shown)),
+            Some(variant) if !is_identifier(&variant) => problems.push(format!(
+                "variant {} of value {} is no identifier",
+                variant, shown
+            )),
+            Some(variant) if !variants.insert(variant.clone()) => {
+                problems.push(format!("variant {} is given twice", variant))
+            }
+            Some(_) => {}
+        }
+
+        if value.labels.is_empty() {
+            problems.push(format!("value {} has no labels", shown));
+        }
+        for label in &value.labels {
+            let label = label.trim().to_lowercase();
+            if label.is_empty() {
+                problems.push(format!("value {} has an empty label", shown));
+            } else if let Some(other) = labels.insert(label.clone(), shown.clone()) {
+                problems.push(format!(
+                    "label {} is given for the values {} and {}",
+                    label, other, shown
+                ));
+            }
+        }
+    }
+    problems
+}
+
+fn is_identifier(s: &str) -> bool {
+    s.chars()
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_')
+        && s.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
+
+/// All definitions in data/.
+pub fn definitions() -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir("data").map_err(|e| format!("data: {}", e))?;
+    let mut paths: Vec<PathBuf> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.to_string_lossy().ends_with(".columns.json"))
+        .collect();
+    paths.sort();
+    Ok(paths)
+}
+
+/// Checks the given definitions or all of data/, prints every problem and returns whether all
+/// definitions are valid.
+pub fn lint(paths: &[String]) -> bool {
+    let mut paths: Vec<PathBuf> = paths.iter().map(PathBuf::from).collect();
+    if paths.is_empty() {
+        paths = match definitions() {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!("{}", e);
+                return false;
+            }
+        };
+    }
+
+    let mut valid = true;
+    for path in paths {
+        let problems = match Definition::load(&path) {
+            Ok(definition) => problems(&definition),
+            Err(e) => vec![e],
+        };
+        for problem in &problems {
+            eprintln!("{}: {}", path.display(), problem);
+        }
+        valid &= problems.is_empty();
+    }
+    valid
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::definition::Definition;
+    use crate::lint::problems;
+
+    fn definition(json: &str) -> Definition {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_valid_definition() {
+        let d = definition(
+            r#"{"groupKey": "id", "headerRowNumber": 1, "dataStartRowNumber": 2, "columns": {
+                "id": {"name": "Id", "type": "string"},
+                "status": {"name": "Status", "type": "enum", "enum": "status::Status"}
+            }}"#,
+        );
+        assert_eq!(problems(&d), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_problems() {
+        let d = definition(
+            r#"{"groupKey": "customer", "columns": {
+                "id": {"name": "Id", "aliases": ["Nr"], "type": "string"},
+                "number": {"name": "nr", "type": "float", "patterns": ["("]},
+                "status": {"name": "Status", "type": "enum"}
+            }}"#,
+        );
+        let problems = problems(&d);
+        assert_eq!(problems.len(), 6, "{:?}", problems);
+        assert!(problems[0].starts_with("headerRowNumber is missing"));
+        assert!(problems[1].starts_with("dataStartRowNumber 0"));
+        assert_eq!(problems[2], "groupKey customer is not among the columns");
+        assert!(problems[3].starts_with("column number: pattern ( is invalid"));
+        assert_eq!(
+            problems[4],
+            "header nr is claimed by the columns id and number"
+        );
+        assert!(problems[5].starts_with("column status: type enum needs"));
+    }
+
+    #[test]
+    fn test_value_problems() {
+        let d = definition(
+            r#"{"groupKey": "id", "headerRowNumber": 1, "dataStartRowNumber": 2, "columns": {
+                "id": {"name": "Id", "type": "string", "values": [
+                    {"value": "a", "labels": ["A"]}
+                ]},
+                "rating": {"name": "Bewertung", "type": "enum", "enum": "rating::Rating", "values": [
+                    {"value": 1, "labels": ["gut"]},
+                    {"value": "bad", "labels": [" Gut", ""]}
+                ]}
+            }}"#,
+        );
+        assert_eq!(
+            problems(&d),
+            vec![
+                "column id: values are only used with type enum",
+                "column rating: values are either all names or all numbers",
+                "column rating: value 1 needs a variant",
+                "column rating: label gut is given for the values 1 and bad",
+                "column rating: value bad has an empty label",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_hook_problems() {
+        let d = definition(
+            r#"{"groupKey": "id", "headerRowNumber": 1, "dataStartRowNumber": 2,
+                "computed": {"id": "string", "retry": "datetime"},
+                "stopWhen": {"column": "summe", "equals": "Summe"},
+                "columns": {"id": {"name": "Id", "type": "string"}}}"#,
+        );
+        assert_eq!(
+            problems(&d),
+            vec![
+                "computed id is also a column",
+                "computed fields need a postRow hook that sets them",
+                "stopWhen column summe is not among the columns",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_key_problems() {
+        let d = definition(
+            r#"{"groupKey": ["id", "id", "invoice", "status", "period"],
+                "headerRowNumber": 1, "dataStartRowNumber": 2,
+                "computed": {"period": "string"}, "postRow": "set_period",
+                "columns": {
+                    "id": {"name": "Id", "type": "string"},
+                    "invoice": {"name": "Rechnung", "type": "string", "optional": true},
+                    "status": {"name": "Status", "type": "enum", "enum": "status::Status"}
+            }}"#,
+        );
+        assert_eq!(
+            problems(&d),
+            vec![
+                "groupKey id is given twice",
+                "groupKey status can't be an enum column",
+            ]
+        );
+        let d = definition(r#"{"groupKey": [], "columns": {}}"#);
+        assert!(problems(&d).contains(&"groupKey has no fields".to_string()));
+    }
+
+    #[test]
+    fn test_definitions_in_data_are_valid() {
+        assert!(crate::lint::lint(&[]));
+    }
+}
@@ -0,0 +1,487 @@
+extern crate serde_json;
+
+mod definition;
+mod infer;
+mod lint;
+mod scaffold;
+
+use convert_case::{Case, Casing};
+use definition::{ColumnDefinition, Definition, Kind, Value};
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tera::Context;
+use tera::Tera;
+
+fn type_hint(d: &ColumnDefinition) -> String {
+    let t = match d.kind {
+        Kind::Enum => d.enum_path().map_or("", |(_, name)| name),
+        ref kind => rust_type(kind),
+    };
+
+    if d.optional {
+        format!("Option<{}>", t)
+    } else {
+        t.to_string()
+    }
+}
+
+fn rust_type(kind: &Kind) -> &'static str {
+    match kind {
+        Kind::Date => "NaiveDate",
+        Kind::Time => "NaiveTime",
+        Kind::DateTime => "NaiveDateTime",
+        Kind::Float => "f64",
+        Kind::Integer => "i64",
+        Kind::Boolean => "bool",
+        Kind::Decimal => "Decimal",
+        Kind::String => "String",
+        Kind::Meterpoint => "Meterpoint",
+        // enums are named by the column
+        Kind::Enum => "",
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct Column {
+    field_name: String,
+    header_name: String,
+    kind: Kind,
+    type_hint: String,
+    enum_: Option<String>,
+    enum_path: Option<String>,
+    enum_mod: Option<String>,
+    /// other spellings of the header
+    aliases: Vec<String>,
+    patterns: Vec<String>,
+    /// values that set a flag
+    yes: Vec<String>,
+    optional: bool,
+    /// set by the post row hook instead of read from the sheet
+    skip: bool,
+}
+
+/// Field of the row struct that holds the column `key`.
+fn field_name(key: &str) -> String {
+    let field_name = key.to_case(Case::Snake);
+    // todo quick fix...
+    if field_name == "type" {
+        return "_type".to_string();
+    }
+    field_name
+}
+
+impl Column {
+    fn new(key: &str, d: &ColumnDefinition) -> Self {
+        Column {
+            field_name: field_name(key),
+            header_name: d.header_name.clone(),
+            type_hint: type_hint(d),
+            kind: d.kind.clone(),
+            aliases: d
+                .names()
+                .skip_while(|name| **name == d.header_name)
+                .map(|alias| alias.trim().to_string())
+                .collect(),
+            patterns: d.patterns.clone(),
+            yes: d.yes.clone(),
+            optional: d.optional,
+            enum_: d.enum_path().map(|(_, name)| name.to_string()),
+            enum_path: d.enum_.clone(),
+            enum_mod: d.enum_path().map(|(module, _)| module.to_string()),
+            skip: false,
+        }
+    }
+
+    fn computed(key: &str, kind: &Kind) -> Self {
+        Column {
+            field_name: field_name(key),
+            header_name: String::new(),
+            kind: kind.clone(),
+            type_hint: format!("Option<{}>", rust_type(kind)),
+            enum_: None,
+            enum_path: None,
+            enum_mod: None,
+            aliases: vec![],
+            patterns: vec![],
+            yes: vec![],
+            optional: true,
+            skip: true,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct TemplateContext {
+    modules: Vec<String>,
+    uses: Vec<String>,
+    columns: Vec<Column>,
+    header_row_number: usize,
+    data_start_row_number: usize,
+    group_key: Vec<KeyPart>,
+    kind: Kind,
+    sheet_name: Option<String>,
+    sheet_pattern: Option<String>,
+    all_sheets: bool,
+    post_row: Option<String>,
+    /// rust literals of the header and value that end the data
+    stop_header: Option<String>,
+    stop_value: Option<String>,
+}
+
+/// Field of the group key and the expression of its value in `from_range`.
+#[derive(Debug, Deserialize, Serialize)]
+struct KeyPart {
+    name: String,
+    value: String,
+}
+
+#[derive(Debug, Serialize)]
+struct Variant {
+    name: String,
+    /// rust literal of the serialised value
+    value: String,
+    /// rust literals of the lowercase labels
+    labels: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct EnumContext {
+    name: String,
+    repr: bool,
+    variants: Vec<Variant>,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("lint") => {
+            if !lint::lint(&args[1..]) {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some("--check") => {
+            if !check(&args[1..]) {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+        Some("generate") if args.len() == 2 => generate(&args[1]),
+        Some("infer") if (2..=3).contains(&args.len()) => {
+            infer::run(&args[1], args.get(2).map(String::as_str))
+        }
+        Some(path) if args.len() == 1 => load(path)
+            .and_then(|definition| importer(&definition, &templates()?))
+            .and_then(format)
+            .map(|code| print!("{}", code)),
+        _ => {
+            eprintln!("Usage: create-import <definition.columns.json>");
+            eprintln!("       create-import generate <definition.columns.json>");
+            eprintln!("       create-import --check [<definition.columns.json>...]");
+            eprintln!("       create-import lint [<definition.columns.json>...]");
+            eprintln!("       create-import infer <workbook.xlsx> [<definition.columns.json>]");
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Definition at `path`, invalid definitions are refused with their problems.
+fn load(path: &str) -> Result<Definition, String> {
+    let definition = Definition::load(path).map_err(|e| format!("{}: {}", path, e))?;
+    let problems = lint::problems(&definition);
+    if !problems.is_empty() {
+        return Err(problems
+            .iter()
+            .map(|problem| format!("{}: {}", path, problem))
+            .collect::<Vec<_>>()
+            .join("\n"));
+    }
+    Ok(definition)
+}
+
+fn templates() -> Result<Tera, String> {
+    Tera::new("templates/**/*.tera").map_err(|e| format!("Parsing error(s): {}", e))
+}
+
+/// Formats generated code like `cargo fmt` does.
+fn format(code: String) -> Result<String, String> {
+    let mut rustfmt = Command::new("rustfmt")
+        .args(["--edition", "2021"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("rustfmt: {}", e))?;
+    rustfmt
+        .stdin
+        .take()
+        .ok_or("rustfmt: no stdin")?
+        .write_all(code.as_bytes())
+        .map_err(|e| format!("rustfmt: {}", e))?;
+
+    let output = rustfmt
+        .wait_with_output()
+        .map_err(|e| format!("rustfmt: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "rustfmt: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| format!("rustfmt: {}", e))
+}
+
+/// Files generated from data/<name>.columns.json: the importer, its enum modules and generated
+/// tests in src/<name>/. The hand-written tests and hooks of the importer are not generated.
+fn render(path: &str) -> Result<Vec<(PathBuf, String)>, String> {
+    let name = Path::new(path)
+        .file_name()
+        .and_then(|name| name.to_str()?.strip_suffix(".columns.json"))
+        .ok_or_else(|| format!("{}: expected a file named <name>.columns.json", path))?;
+    let dir = Path::new("src").join(name);
+    let definition = load(path)?;
+    let tera = templates()?;
+
+    let mut files = vec![
+        (dir.join("mod.rs"), importer(&definition, &tera)?),
+        (
+            dir.join("generated_tests.rs"),
+            scaffold::tests(&definition, &tera)?,
+        ),
+    ];
+    for (module, code) in enums(&definition, &tera)? {
+        files.push((dir.join(format!("{}.rs", module)), code));
+    }
+    files
+        .into_iter()
+        .map(|(path, code)| Ok((path, format(code)?)))
+        .collect()
+}
+
+/// Writes the files generated from data/<name>.columns.json, see [`render`].
+fn generate(path: &str) -> Result<(), String> {
+    for (file, code) in render(path)? {
+        if let Some(dir) = file.parent() {
+            std::fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+        }
+        std::fs::write(&file, code).map_err(|e| format!("{}: {}", file.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Compares the generated files of the given definitions or all of data/ with the sources,
+/// prints the first difference of every file that drifted and returns whether none did.
+fn check(paths: &[String]) -> bool {
+    let paths = if paths.is_empty() {
+        match lint::definitions() {
+            Ok(paths) => paths,
+            Err(e) => {
+                eprintln!("{}", e);
+                return false;
+            }
+        }
+    } else {
+        paths.iter().map(PathBuf::from).collect()
+    };
+
+    let mut same = true;
+    for path in paths {
+        let path = path.to_string_lossy();
+        let files = match render(&path) {
+            Ok(files) => files,
+            Err(e) => {
+                eprintln!("{}", e);
+                same = false;
+                continue;
+            }
+        };
+        for (file, generated) in files {
+            let source = std::fs::read_to_string(&file).unwrap_or_default();
+            if let Some(difference) = difference(&source, &generated) {
+                eprintln!("{} differs from {}, {}", file.display(), path, difference);
+                same = false;
+            }
+        }
+    }
+    same
+}
+
+/// First line in which `source` and `generated` differ.
+fn difference(source: &str, generated: &str) -> Option<String> {
+    let mut source = source.lines();
+    let mut generated = generated.lines();
+    for number in 1.. {
+        match (source.next(), generated.next()) {
+            (None, None) => return None,
+            (a, b) if a == b => continue,
+            (a, b) => {
+                return Some(format!(
+                    "line {}:\n- {}\n+ {}",
+                    number,
+                    a.unwrap_or_default(),
+                    b.unwrap_or_default()
+                ))
+            }
+        }
+    }
+    None
+}
+
+/// Enum modules of the columns that give their values.
+fn enums(definition: &Definition, tera: &Tera) -> Result<Vec<(String, String)>, String> {
+    let mut modules = vec![];
+    for column in definition.columns.values() {
+        let (module, name) = match column.enum_path() {
+            Some(path) if !column.values.is_empty() => path,
+            _ => continue,
+        };
+        let variants = column
+            .values
+            .iter()
+            .map(|value| Variant {
+                name: value.variant().unwrap_or_default(),
+                value: match &value.value {
+                    Value::Name(name) => format!("{:?}", name),
+                    Value::Number(number) => number.to_string(),
+                },
+                labels: value
+                    .labels
+                    .iter()
+                    .map(|label| format!("{:?}", label.trim().to_lowercase()))
+                    .collect(),
+            })
+            .collect();
+        let data = EnumContext {
+            name: name.to_string(),
+            repr: matches!(column.values[0].value, Value::Number(_)),
+            variants,
+        };
+
+        let context = Context::from_serialize(&data).map_err(|e| e.to_string())?;
+        let code = tera
+            .render("enum.rs.tera", &context)
+            .map_err(|e| e.to_string())?;
+        modules.push((module.to_string(), code));
+    }
+    Ok(modules)
+}
+
+/// Renders the importer of a definition.
+fn importer(definition: &Definition, tera: &Tera) -> Result<String, String> {
+    let mut modules: Vec<String> = Vec::new();
+    let mut uses: Vec<String> = Vec::new();
+    let mut columns: Vec<Column> = definition
+        .columns
+        .iter()
+        .map(|(key, column)| Column::new(key, column))
+        .collect();
+    columns.extend(
+        definition
+            .computed
+            .iter()
+            .map(|(key, kind)| Column::computed(key, kind)),
+    );
+    for column in &columns {
+        match column.kind {
+            Kind::Enum => {
+                modules.extend(column.enum_mod.clone());
+                uses.extend(column.enum_path.clone());
+            }
+            Kind::Date => uses.push("chrono::NaiveDate".to_string()),
+            Kind::Time => uses.push("chrono::NaiveTime".to_string()),
+            Kind::DateTime => uses.push("chrono::NaiveDateTime".to_string()),
+            Kind::Meterpoint => uses.push("crate::meterpoint::Meterpoint".to_string()),
+            Kind::Decimal => uses.push("crate::decimal::Decimal".to_string()),
+            _ => {}
+        }
+    }
+    if definition.post_row.is_some() {
+        modules.push("hooks".to_string());
+    }
+    modules.sort();
+    modules.dedup();
+    uses.sort();
+    uses.dedup();
+    columns.sort_by_key(|c| c.field_name.clone());
+
+    let group_key = definition
+        .group_key
+        .fields()
+        .iter()
+        .map(|key| {
+            let field = field_name(key.trim());
+            let value = match definition.columns.get(key) {
+                Some(column) if column.optional => format!(
+                    "r.{}.as_ref().map(ToString::to_string).unwrap_or_default()",
+                    field
+                ),
+                Some(column) if column.kind == Kind::String => format!("r.{}.clone()", field),
+                Some(_) => format!("r.{}.to_string()", field),
+                // computed fields are optional
+                None => format!(
+                    "r.{}.as_ref().map(ToString::to_string).unwrap_or_default()",
+                    field
+                ),
+            };
+            KeyPart {
+                name: key.clone(),
+                value,
+            }
+        })
+        .collect();
+
+    let stop_when = definition.stop_when.as_ref().map(|stop_when| {
+        let header = &definition.columns[&stop_when.column].header_name;
+        (header, &stop_when.equals)
+    });
+
+    let data = TemplateContext {
+        columns,
+        header_row_number: definition.header_row_number - 1,
+        data_start_row_number: definition.data_start_row_number - 1,
+        group_key,
+        modules,
+        uses,
+        kind: Kind::String,
+        sheet_name: definition.sheet_name.clone(),
+        sheet_pattern: definition.sheet_pattern.clone(),
+        all_sheets: definition.all_sheets,
+        post_row: definition.post_row.clone(),
+        stop_header: stop_when.map(|(header, _)| format!("{:?}", header)),
+        stop_value: stop_when.map(|(_, value)| format!("{:?}", value)),
+    };
+
+    let context = Context::from_serialize(&data).map_err(|e| e.to_string())?;
+    tera.render("importer.rs.tera", &context)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{check, difference};
+
+    #[test]
+    fn test_difference() {
+        assert_eq!(difference("a\nb\n", "a\nb\n"), None);
+        assert_eq!(
+            difference("a\nb\n", "a\nc\nd\n"),
+            Some("line 2:\n- b\n+ c".to_string())
+        );
+        assert_eq!(
+            difference("a\n", "a\nb\n"),
+            Some("line 2:\n- \n+ b".to_string())
+        );
+    }
+
+    #[test]
+    fn test_sources_match_definitions() {
+        assert!(check(&[]));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::definition::{ColumnDefinition, Definition, Kind, Value};
+use crate::field_name;
+use serde::Serialize;
+use tera::{Context, Tera};
+
+const METERPOINT: &str = "AT0020000000000000000000000012345";
+
+#[derive(Debug, Serialize)]
+struct Column {
+    field_name: String,
+    /// rust literals of the name and aliases of the header
+    names: Vec<String>,
+    /// cell of the column in the fixture row
+    fixture: Option<String>,
+    /// what the value of the fixture is serialised as
+    expected: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct TestContext {
+    columns: Vec<Column>,
+    header_row: usize,
+    data_start_row: usize,
+    group_value: String,
+    fixture: bool,
+}
+
+fn text(value: &str) -> String {
+    format!("text({:?})", value)
+}
+
+/// Cell and serialised value of a sample value of the column, enums without values can't be
+/// made up.
+fn fixture(field_name: &str, d: &ColumnDefinition) -> Option<(String, String)> {
+    let (cell, expected) = match d.kind {
+        Kind::String => (text(field_name), format!("{:?}", field_name)),
+        Kind::Date => (
+            "DataType::DateTime(45291.0)".into(),
+            "\"2023-12-31\"".into(),
+        ),
+        Kind::Time => ("DataType::DateTime(0.5)".into(), "\"12:00:00\"".into()),
+        Kind::DateTime => (
+            "DataType::DateTime(45291.5)".into(),
+            "\"2023-12-31T12:00:00\"".into(),
+        ),
+        Kind::Float => ("DataType::Float(1.5)".into(), "1.5".into()),
+        Kind::Decimal => ("DataType::Float(13.35092)".into(), "\"13.35092\"".into()),
+        Kind::Integer => ("DataType::Int(3)".into(), "3".into()),
+        Kind::Boolean => (
+            text(d.yes.first().map_or("X", String::as_str)),
+            "true".into(),
+        ),
+        Kind::Meterpoint => (text(METERPOINT), format!("{:?}", METERPOINT)),
+        Kind::Enum => {
+            let value = d.values.first()?;
+            let expected = match &value.value {
+                Value::Name(name) => format!("{:?}", name),
+                Value::Number(number) => number.to_string(),
+            };
+            (text(value.labels.first()?), expected)
+        }
+    };
+    Some((cell, expected))
+}
+
+/// How the value of [`fixture`] is shown in a group key.
+fn key_value(field_name: &str, d: &ColumnDefinition) -> Option<String> {
+    let value = match d.kind {
+        Kind::String => field_name,
+        Kind::Date => "2023-12-31",
+        Kind::Time => "12:00:00",
+        Kind::DateTime => "2023-12-31 12:00:00",
+        Kind::Float => "1.5",
+        Kind::Decimal => "13.35092",
+        Kind::Integer => "3",
+        Kind::Boolean => "true",
+        Kind::Meterpoint => METERPOINT,
+        Kind::Enum => return None,
+    };
+    Some(value.to_string())
+}
+
+/// Test module of the importer that checks the headers and reads a row of sample values.
+pub fn tests(definition: &Definition, tera: &Tera) -> Result<String, String> {
+    let mut columns: Vec<Column> = definition
+        .columns
+        .iter()
+        .map(|(key, d)| {
+            let field_name = field_name(key);
+            let fixture = fixture(&field_name, d);
+            Column {
+                names: d.names().map(|name| format!("{:?}", name.trim())).collect(),
+                fixture: fixture.as_ref().map(|(cell, _)| cell.clone()),
+                expected: fixture.map(|(_, expected)| expected),
+                field_name,
+            }
+        })
+        .collect();
+    columns.sort_by(|a, b| a.field_name.cmp(&b.field_name));
+
+    // computed fields are set by hooks, their value is not known here
+    let group_value: Option<Vec<String>> = definition
+        .group_key
+        .fields()
+        .iter()
+        .map(|key| key_value(&field_name(key), definition.columns.get(key)?))
+        .collect();
+
+    let data = TestContext {
+        fixture: columns.iter().all(|c| c.fixture.is_some()) && group_value.is_some(),
+        header_row: definition.header_row_number - 1,
+        data_start_row: definition.data_start_row_number - 1,
+        group_value: format!("{:?}", group_value.unwrap_or_default().join("/")),
+        columns,
+    };
+
+    let context = Context::from_serialize(&data).map_err(|e| e.to_string())?;
+    tera.render("tests.rs.tera", &context)
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,131 @@
+//! Generated by create-import from the definition of the importer, changes are overwritten.
+
+use super::{from_range, Row};
+use crate::row::column_map;
+use crate::ImportError;
+use calamine::{DataType, Range};
+
+/// Names and aliases of every column in the order of [`Row`].
+const NAMES: &[&[&str]] = &[
+    &["Sparte"],
+    &["Abrmenge"],
+    &["Vertragskonto"],
+    &["TWährg"],
+    &["Buch.dat."],
+    &["Zählpunkt", "ZP"],
+    &["Name"],
+    &["Nettobetrag"],
+    &["Preisbetrag"],
+    &["Druckbeleg"],
+    &["StGrBt"],
+    &["Geschäftspartner"],
+    &["Gültig ab"],
+    &["Gültig bis"],
+];
+
+fn text(value: &str) -> DataType {
+    DataType::String(value.to_string())
+}
+
+fn headers() -> Vec<DataType> {
+    NAMES.iter().map(|names| text(names[0])).collect()
+}
+
+#[test]
+fn test_every_name_and_alias_is_accepted() {
+    for (column, names) in NAMES.iter().enumerate() {
+        for name in names.iter() {
+            let mut headers = headers();
+            headers[column] = text(name);
+            let columns = column_map::<Row>(&headers).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!(columns[column], column, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_unknown_header_is_rejected() {
+    let mut headers = headers();
+    headers.push(text("Unbekannte Spalte"));
+    assert!(matches!(
+        column_map::<Row>(&headers),
+        Err(ImportError::UnknownHeader(ref header)) if header == "Unbekannte Spalte"
+    ));
+}
+
+#[test]
+fn test_fixture_row() {
+    let cells = [
+        text("_type"),
+        DataType::Float(1.5),
+        text("contract_account"),
+        text("currency"),
+        DataType::DateTime(45291.0),
+        text("AT0020000000000000000000000012345"),
+        text("name"),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        text("print_receipt"),
+        text("stgrbt"),
+        text("supplier_customer_id"),
+        DataType::DateTime(45291.0),
+        DataType::DateTime(45291.0),
+    ];
+    let last = (cells.len() - 1) as u32;
+    let mut sheet = Range::new((0, 0), (7, last));
+    for (i, header) in headers().into_iter().enumerate() {
+        sheet.set_value((6, i as u32), header);
+    }
+    for (i, cell) in cells.into_iter().enumerate() {
+        sheet.set_value((7, i as u32), cell);
+    }
+
+    let groups = from_range(&sheet).unwrap();
+    let rows = groups.get("supplier_customer_id").unwrap();
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(serde_json::json!(row._type), serde_json::json!("_type"));
+    assert_eq!(
+        serde_json::json!(row.billing_amount),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.contract_account),
+        serde_json::json!("contract_account")
+    );
+    assert_eq!(
+        serde_json::json!(row.currency),
+        serde_json::json!("currency")
+    );
+    assert_eq!(
+        serde_json::json!(row.entry_date),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.meterpoint),
+        serde_json::json!("AT0020000000000000000000000012345")
+    );
+    assert_eq!(serde_json::json!(row.name), serde_json::json!("name"));
+    assert_eq!(
+        serde_json::json!(row.net_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(serde_json::json!(row.price), serde_json::json!("13.35092"));
+    assert_eq!(
+        serde_json::json!(row.print_receipt),
+        serde_json::json!("print_receipt")
+    );
+    assert_eq!(serde_json::json!(row.stgrbt), serde_json::json!("stgrbt"));
+    assert_eq!(
+        serde_json::json!(row.supplier_customer_id),
+        serde_json::json!("supplier_customer_id")
+    );
+    assert_eq!(
+        serde_json::json!(row.valid_from),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.valid_to),
+        serde_json::json!("2023-12-31")
+    );
+}
@@ -1,9 +1,16 @@
-use crate::commission::run;
+use crate::commission::{from_range, run, selection};
+use crate::decimal::Decimal;
+use crate::{ImportError, Reason};
+use calamine::{DataType, Range};
 use chrono::NaiveDate;
 
+fn decimal(value: &str) -> Option<Decimal> {
+    Decimal::parse(value)
+}
+
 #[test]
 fn test_get_column_map_success_with_ordered_columns() {
-    let result = run("var/commission.xlsx");
+    let result = run("var/commission.xlsx", &selection());
     assert!(result.is_ok());
 
     let result = result.unwrap();
@@ -23,10 +30,13 @@ fn test_get_column_map_success_with_ordered_columns() {
         rows[0].entry_date,
         NaiveDate::from_ymd_opt(2022, 08, 16).unwrap()
     );
-    assert_eq!(rows[0].meterpoint, "AT0010000000000000001000004107355");
+    assert_eq!(
+        rows[0].meterpoint.as_ref().unwrap(),
+        &"AT0010000000000000001000004107355"
+    );
     assert_eq!(rows[0].name, "Company");
-    assert_eq!(rows[0].net_amount, "13.35092".to_string());
-    assert_eq!(rows[0].price, "0.00044".to_string());
+    assert_eq!(rows[0].net_amount, decimal("13.35092"));
+    assert_eq!(rows[0].price, decimal("0.00044"));
     assert_eq!(rows[0].print_receipt, "300051234");
     assert_eq!(rows[0].stgrbt, "SBPROV");
     assert_eq!(rows[0].supplier_customer_id, "123456789");
@@ -40,7 +50,76 @@ fn test_get_column_map_success_with_ordered_columns() {
         NaiveDate::from_ymd_opt(2021, 12, 31).unwrap()
     );
 
-    assert_eq!(rows[1].net_amount, "8".to_string());
-    assert_eq!(rows[2].net_amount, "87.1299".to_string());
-    assert_eq!(rows[3].net_amount, "159.8535".to_string());
+    assert_eq!(rows[1].net_amount, decimal("8"));
+    assert_eq!(rows[2].net_amount, decimal("87.1299"));
+    assert_eq!(rows[3].net_amount, decimal("159.8535"));
+}
+
+/// Header in row 7 like the SAP export, one data row with the given amount cells.
+fn sheet(net_amount: DataType, price: DataType) -> Range<DataType> {
+    let row: Vec<(&str, DataType)> = vec![
+        ("Abrmenge", DataType::Float(1.0)),
+        ("Vertragskonto", DataType::String("1".to_string())),
+        ("TWährg", DataType::String("EUR".to_string())),
+        ("Buch.dat.", DataType::DateTime(44789.0)),
+        (
+            "Zählpunkt",
+            DataType::String("AT0010000000000000001000004107355".to_string()),
+        ),
+        ("Name", DataType::String("Company".to_string())),
+        ("Nettobetrag", net_amount),
+        ("Preisbetrag", price),
+        ("Druckbeleg", DataType::String("300051234".to_string())),
+        ("StGrBt", DataType::String("SBPROV".to_string())),
+        ("Geschäftspartner", DataType::String("1".to_string())),
+        ("Sparte", DataType::String("Strom".to_string())),
+        ("Gültig ab", DataType::DateTime(44317.0)),
+        ("Gültig bis", DataType::DateTime(44561.0)),
+    ];
+
+    let mut range = Range::new((0, 0), (7, row.len() as u32 - 1));
+    for (i, (header, value)) in row.into_iter().enumerate() {
+        range.set_value((6, i as u32), DataType::String(header.to_string()));
+        range.set_value((7, i as u32), value);
+    }
+    range
+}
+
+#[test]
+fn test_amounts_are_parsed_as_decimals() {
+    let parse = |net_amount: &str, price: DataType| {
+        let groups = from_range(&sheet(DataType::String(net_amount.to_string()), price))?;
+        let row = &groups.get("1").unwrap()[0];
+        Ok::<_, ImportError>((row.net_amount, row.price))
+    };
+
+    assert_eq!(
+        parse("1.234,56", DataType::Float(0.00044)).unwrap(),
+        (decimal("1234.56"), decimal("0.00044"))
+    );
+    assert_eq!(
+        parse("12,30-", DataType::String("0,5".to_string())).unwrap(),
+        (decimal("-12.3"), decimal("0.5"))
+    );
+    assert_eq!(parse("", DataType::Empty).unwrap(), (None, None));
+    assert!(matches!(
+        parse("n/a", DataType::Empty),
+        Err(ImportError::ValueError(7, ref column, ref value, Reason::ParseFailure(_)))
+            if column == "Nettobetrag" && value == "n/a"
+    ));
+    assert!(parse("1", DataType::Bool(true)).is_err());
+}
+
+#[test]
+fn test_meterpoint_may_be_blank() {
+    let mut range = sheet(DataType::Empty, DataType::Empty);
+    range.set_value((7, 4), DataType::Empty);
+    let groups = from_range(&range).unwrap();
+    assert_eq!(groups.get("1").unwrap()[0].meterpoint, None);
+
+    range.set_value((7, 4), DataType::String("AT001".to_string()));
+    assert!(matches!(
+        from_range(&range),
+        Err(ImportError::ValueError(7, ref column, _, _)) if column == "Zählpunkt"
+    ));
 }
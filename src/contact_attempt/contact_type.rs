@@ -1,3 +1,6 @@
+use crate::row::FromCell;
+use crate::Reason;
+use calamine::DataType;
 use serde::Serialize;
 
 #[derive(Serialize, Debug)]
@@ -9,6 +12,7 @@ pub(crate) enum ContactType {
     #[serde(rename = "phone")]
     Phone,
 }
+
 impl ContactType {
     pub(crate) fn from_excel_value(v: String) -> Result<Option<ContactType>, String> {
         match v.to_lowercase().trim() {
@@ -20,3 +24,9 @@ impl ContactType {
         }
     }
 }
+
+impl FromCell for ContactType {
+    fn from_cell(cell: &DataType) -> Result<Option<ContactType>, Reason> {
+        ContactType::from_excel_value(cell.to_string()).map_err(|_| Reason::UnknownEnumValue)
+    }
+}
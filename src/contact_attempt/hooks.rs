@@ -0,0 +1,7 @@
+use super::Row;
+use chrono::NaiveDateTime;
+
+/// Date and time of the next attempt are separate columns but one value for the admin.
+pub fn combine_retry(r: &mut Row) {
+    r.retry = Some(NaiveDateTime::new(r.retry_date, r.retry_time));
+}
@@ -1,15 +1,21 @@
 mod contact_type;
+mod hooks;
 mod rating;
 mod result;
 mod status;
 
+#[cfg(test)]
+mod generated_tests;
 #[cfg(test)]
 mod tests;
 
+use crate::excel;
+use crate::group::{Groups, Key};
+use crate::row::{self, ExcelRow};
+use crate::source;
 use crate::ImportError;
-use calamine::{open_workbook_auto, DataType, Reader};
+use calamine::{DataType, Range};
 use serde::Serialize;
-use std::collections::HashMap;
 use std::fmt::Debug;
 
 use chrono::NaiveDate;
@@ -20,221 +26,90 @@ use rating::Rating;
 use result::Result_;
 use status::Status;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ExcelRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Row {
+    #[excel(header = "contactType")]
     contact_type: ContactType,
+    #[excel(header = "createdBy")]
     created_by: String,
+    #[excel(header = "Rückmeldung")]
     feedback: String,
+    #[excel(header = "projectContactId")]
     project_contact_id: String,
+    #[excel(header = "Bewertung")]
     rating: Option<Rating>,
+    #[excel(header = "Ergebnis")]
     result: Result_,
+    #[excel(skip)]
+    retry: Option<NaiveDateTime>,
+    #[excel(header = "Wiedervorlage Datum")]
     retry_date: NaiveDate,
+    #[excel(header = "Wiedervorlage Zeit")]
     retry_time: NaiveTime,
-    retry: Option<NaiveDateTime>,
+    #[excel(header = "status")]
     status: Status,
 }
 
-#[derive(Debug)]
-enum Column {
-    ContactType,
-    CreatedBy,
-    Feedback,
-    ProjectContactId,
-    Rating,
-    Result,
-    RetryDate,
-    RetryTime,
-    Status,
-}
-
-impl Into<usize> for Column {
-    fn into(self) -> usize {
-        self as usize
+/// Sheets read unless another one is given on the command line.
+pub fn selection() -> source::Selection {
+    source::Selection {
+        sheet: source::Sheet::First,
+        all: false,
     }
 }
 
-impl Column {
-    fn usize(self) -> usize {
-        self as usize
-    }
+/// Rows of all selected sheets, errors carry the name of the sheet they occurred in.
+pub fn run<P: AsRef<std::path::Path>>(
+    path: P,
+    selection: &source::Selection,
+) -> Result<Groups<Row>, ImportError> {
+    from_sheets(source::sheets(path, selection)?, selection)
 }
 
-fn get_column_map(headers: Vec<String>) -> Result<Vec<usize>, ImportError> {
-    let mut map: Vec<Option<usize>> = vec![None; 9];
-    for (i, header) in headers.iter().enumerate() {
-        match header.to_lowercase().trim() {
-            "contacttype" => {
-                map[<Column as Into<usize>>::into(Column::ContactType)] = Some(i);
-            }
-
-            "createdby" => {
-                map[<Column as Into<usize>>::into(Column::CreatedBy)] = Some(i);
-            }
-
-            "rückmeldung" => {
-                map[<Column as Into<usize>>::into(Column::Feedback)] = Some(i);
-            }
-
-            "projectcontactid" => {
-                map[<Column as Into<usize>>::into(Column::ProjectContactId)] = Some(i);
-            }
-
-            "bewertung" => {
-                map[<Column as Into<usize>>::into(Column::Rating)] = Some(i);
-            }
-
-            "ergebnis" => {
-                map[<Column as Into<usize>>::into(Column::Result)] = Some(i);
-            }
-
-            "wiedervorlage datum" => {
-                map[<Column as Into<usize>>::into(Column::RetryDate)] = Some(i);
-            }
-
-            "wiedervorlage zeit" => {
-                map[<Column as Into<usize>>::into(Column::RetryTime)] = Some(i);
-            }
-
-            "status" => {
-                map[<Column as Into<usize>>::into(Column::Status)] = Some(i);
+/// Rows of `sheets` one after the other. When every sheet of the workbook is read, sheets
+/// without the header row of [`Row`], e.g. a cover sheet, are skipped.
+pub fn from_sheets(
+    sheets: Vec<(String, Range<DataType>)>,
+    selection: &source::Selection,
+) -> Result<Groups<Row>, ImportError> {
+    let mut groups: Groups<Row> = Groups::default();
+    let mut matched = false;
+    let mut mismatch = None;
+    for (name, sheet) in sheets {
+        if selection.every_sheet() {
+            if let Err(e) = columns(&sheet) {
+                mismatch.get_or_insert(ImportError::Sheet(name, Box::new(e)));
+                continue;
             }
-
-            _ => return Err(ImportError::UnknownHeader(header.clone())),
         }
+        matched = true;
+        let rows = from_range(&sheet).map_err(|e| ImportError::Sheet(name, Box::new(e)))?;
+        groups.append(rows);
     }
 
-    for (_i, h) in map.clone().into_iter().enumerate() {
-        // todo how to get enum from index _i?
-        match h {
-            Some(_) => {}
-            None => return Err(ImportError::MissingHeader("todo".to_string())),
-        }
+    match mismatch {
+        Some(e) if !matched => Err(e),
+        _ => Ok(groups),
     }
-
-    Ok(map.into_iter().flatten().collect())
 }
 
-pub fn run<P: AsRef<std::path::Path>>(path: P) -> Result<HashMap<String, Vec<Row>>, ImportError> {
-    let mut excel = open_workbook_auto(path)?;
+/// Column of every field of [`Row`], by the header row of `sheet`.
+fn columns(sheet: &Range<DataType>) -> Result<Vec<usize>, ImportError> {
+    row::column_map::<Row>(excel::row(sheet, 0)?)
+}
 
-    // todo input
-    let header_row = 0;
+pub fn from_range(sheet: &Range<DataType>) -> Result<Groups<Row>, ImportError> {
     let data_start_row = 1;
-    let sheet_names = excel.sheet_names().to_vec();
-    let sheet_name = sheet_names.first().unwrap();
-
-    let sheet = excel
-        .worksheet_range(sheet_name)
-        .ok_or_else(|| ImportError::SheetNotFound(sheet_name.to_string()))??;
-
-    let headers: Vec<String> = sheet
-        .rows()
-        .nth(header_row)
-        .unwrap()
-        .iter()
-        .map(|header_cell| {
-            return match header_cell.get_string() {
-                None => String::from(""),
-                Some(v) => v.to_string(),
-            };
-        })
-        .collect();
-    let column_map: Vec<usize> = get_column_map(headers)?;
-
-    let mut groups: HashMap<String, Vec<Row>> = HashMap::new();
-    for (i, row) in sheet.rows().enumerate().skip(data_start_row) {
-        let mut r = transform_row(&column_map, row, i)?;
-
-        r.retry = Some(NaiveDateTime::new(r.retry_date, r.retry_time));
-
-        let k = r.project_contact_id.clone();
-        if !groups.contains_key(&*k) {
-            groups.insert(k.clone(), Vec::new());
-        }
-        groups.get_mut(&*k).unwrap().push(r);
+    let column_map = columns(sheet)?;
+
+    let mut groups: Groups<Row> = Groups::default();
+    for (i, cells) in sheet.rows().enumerate().skip(data_start_row) {
+        let mut r = Row::from_cells(cells, &column_map, i)?;
+        hooks::combine_retry(&mut r);
+        let key = Key::new(vec![("projectContactId", r.project_contact_id.clone())]);
+        groups.push(key, r);
     }
 
     Ok(groups)
 }
-
-fn transform_row(
-    column_map: &[usize],
-    row: &[DataType],
-    row_number: usize,
-) -> Result<Row, ImportError> {
-    let r = Row {
-        contact_type: ContactType::from_excel_value(
-            row[column_map[Column::ContactType.usize()]].to_string(),
-        )
-        .map_err(|e| ImportError::ValueError(row_number, "contactType".to_string(), e))?
-        .ok_or_else(|| {
-            ImportError::ValueError(
-                row_number,
-                "contactType".to_string(),
-                "Cell has no value".to_string(),
-            )
-        })?,
-
-        created_by: row[column_map[Column::CreatedBy.usize()]]
-            .to_string()
-            .trim()
-            .to_string(),
-
-        feedback: row[column_map[Column::Feedback.usize()]]
-            .to_string()
-            .trim()
-            .to_string(),
-
-        project_contact_id: row[column_map[Column::ProjectContactId.usize()]]
-            .to_string()
-            .trim()
-            .to_string(),
-
-        rating: Rating::from_excel_value(row[column_map[Column::Rating.usize()]].to_string())
-            .map_err(|e| ImportError::ValueError(row_number, "Bewertung".to_string(), e))?,
-
-        result: Result_::from_excel_value(row[column_map[Column::Result.usize()]].to_string())
-            .map_err(|e| ImportError::ValueError(row_number, "Ergebnis".to_string(), e))?
-            .ok_or_else(|| {
-                ImportError::ValueError(
-                    row_number,
-                    "Ergebnis".to_string(),
-                    "Cell has no value".to_string(),
-                )
-            })?,
-
-        retry_date: row[column_map[Column::RetryDate.usize()]]
-            .as_date()
-            .ok_or_else(|| {
-                ImportError::ValueError(
-                    row_number,
-                    "Wiedervorlage Datum".to_string(),
-                    "Cell has no value".to_string(),
-                )
-            })?,
-
-        retry_time: row[column_map[Column::RetryTime.usize()]]
-            .as_time()
-            .ok_or_else(|| {
-                ImportError::ValueError(
-                    row_number,
-                    "Wiedervorlage Zeit".to_string(),
-                    "Cell has no value".to_string(),
-                )
-            })?,
-        retry: None,
-
-        status: Status::from_excel_value(row[column_map[Column::Status.usize()]].to_string())
-            .map_err(|e| ImportError::ValueError(row_number, "status".to_string(), e))?
-            .ok_or_else(|| {
-                ImportError::ValueError(
-                    row_number,
-                    "status".to_string(),
-                    "Cell has no value".to_string(),
-                )
-            })?,
-    };
-
-    Ok(r)
-}
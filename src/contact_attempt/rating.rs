@@ -1,3 +1,6 @@
+use crate::row::FromCell;
+use crate::Reason;
+use calamine::DataType;
 use serde_repr::*;
 
 #[derive(Serialize_repr, Deserialize_repr, PartialEq, Debug)]
@@ -21,3 +24,9 @@ impl Rating {
         }
     }
 }
+
+impl FromCell for Rating {
+    fn from_cell(cell: &DataType) -> Result<Option<Rating>, Reason> {
+        Rating::from_excel_value(cell.to_string()).map_err(|_| Reason::UnknownEnumValue)
+    }
+}
@@ -1,3 +1,6 @@
+use crate::row::FromCell;
+use crate::Reason;
+use calamine::DataType;
 use serde::Serialize;
 
 #[derive(Serialize, Debug)]
@@ -35,6 +38,7 @@ pub(crate) enum Result_ {
     #[serde(rename = "offer-created")]
     OfferCreated,
 }
+
 impl Result_ {
     pub(crate) fn from_excel_value(v: String) -> Result<Option<Result_>, String> {
         match v.to_lowercase().trim() {
@@ -60,3 +64,9 @@ impl Result_ {
         }
     }
 }
+
+impl FromCell for Result_ {
+    fn from_cell(cell: &DataType) -> Result<Option<Result_>, Reason> {
+        Result_::from_excel_value(cell.to_string()).map_err(|_| Reason::UnknownEnumValue)
+    }
+}
@@ -1,3 +1,6 @@
+use crate::row::FromCell;
+use crate::Reason;
+use calamine::DataType;
 use serde::Serialize;
 
 #[derive(Serialize, Debug)]
@@ -7,6 +10,7 @@ pub(crate) enum Status {
     #[serde(rename = "todo")]
     Todo,
 }
+
 impl Status {
     pub(crate) fn from_excel_value(v: String) -> Result<Option<Status>, String> {
         match v.to_lowercase().trim() {
@@ -17,3 +21,9 @@ impl Status {
         }
     }
 }
+
+impl FromCell for Status {
+    fn from_cell(cell: &DataType) -> Result<Option<Status>, Reason> {
+        Status::from_excel_value(cell.to_string()).map_err(|_| Reason::UnknownEnumValue)
+    }
+}
@@ -1,8 +1,8 @@
-use crate::contact_attempt::run;
+use crate::contact_attempt::{run, selection};
 
 #[test]
 fn test_get_column_map_success_with_ordered_columns() {
-    let result = run("var/contact_attempt.xlsx");
+    let result = run("var/contact_attempt.xlsx", &selection());
     if !result.is_ok() {
         assert!(result.is_ok(), "{}", result.err().unwrap().to_string());
     }
@@ -17,3 +17,35 @@ fn test_get_column_map_success_with_ordered_columns() {
 
     assert_eq!(rows.len(), 2);
 }
+
+#[test]
+fn test_csv_and_ods_produce_identical_rows() {
+    let csv = run("var/contact_attempt.csv", &selection());
+    assert!(csv.is_ok(), "{}", csv.err().unwrap());
+    let ods = run("var/contact_attempt.ods", &selection());
+    assert!(ods.is_ok(), "{}", ods.err().unwrap());
+
+    let csv = csv.unwrap();
+    assert_eq!(csv.get("1").unwrap().len(), 2);
+    // zero padded ids stay strings
+    assert!(csv.get("0042").is_some());
+
+    assert_eq!(
+        serde_json::to_value(&csv).unwrap(),
+        serde_json::to_value(ods.unwrap()).unwrap()
+    );
+}
+
+/// Output of the csv fixture has to stay byte for byte the same, run with `UPDATE_GOLDEN=1` to
+/// write var/contact_attempt.json after an intended change.
+#[test]
+fn test_output_matches_golden_file() {
+    let groups = run("var/contact_attempt.csv", &selection()).unwrap();
+    let json = serde_json::to_string_pretty(&groups).unwrap() + "\n";
+
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        std::fs::write("var/contact_attempt.json", &json).unwrap();
+    }
+    let golden = std::fs::read_to_string("var/contact_attempt.json").unwrap();
+    assert_eq!(json, golden);
+}
@@ -0,0 +1,66 @@
+//! Generated by create-import from the definition of the importer, changes are overwritten.
+
+use super::{from_range, Row};
+use crate::row::column_map;
+use crate::ImportError;
+use calamine::{DataType, Range};
+
+/// Names and aliases of every column in the order of [`Row`].
+const NAMES: &[&[&str]] = &[&["Kunden ID"], &["Tag ID"], &["Tag Wert"]];
+
+fn text(value: &str) -> DataType {
+    DataType::String(value.to_string())
+}
+
+fn headers() -> Vec<DataType> {
+    NAMES.iter().map(|names| text(names[0])).collect()
+}
+
+#[test]
+fn test_every_name_and_alias_is_accepted() {
+    for (column, names) in NAMES.iter().enumerate() {
+        for name in names.iter() {
+            let mut headers = headers();
+            headers[column] = text(name);
+            let columns = column_map::<Row>(&headers).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!(columns[column], column, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_unknown_header_is_rejected() {
+    let mut headers = headers();
+    headers.push(text("Unbekannte Spalte"));
+    assert!(matches!(
+        column_map::<Row>(&headers),
+        Err(ImportError::UnknownHeader(ref header)) if header == "Unbekannte Spalte"
+    ));
+}
+
+#[test]
+fn test_fixture_row() {
+    let cells = [text("customer_id"), text("tag_id"), text("tag_value")];
+    let last = (cells.len() - 1) as u32;
+    let mut sheet = Range::new((0, 0), (1, last));
+    for (i, header) in headers().into_iter().enumerate() {
+        sheet.set_value((0, i as u32), header);
+    }
+    for (i, cell) in cells.into_iter().enumerate() {
+        sheet.set_value((1, i as u32), cell);
+    }
+
+    let groups = from_range(&sheet).unwrap();
+    let rows = groups.get("customer_id").unwrap();
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(
+        serde_json::json!(row.customer_id),
+        serde_json::json!("customer_id")
+    );
+    assert_eq!(serde_json::json!(row.tag_id), serde_json::json!("tag_id"));
+    assert_eq!(
+        serde_json::json!(row.tag_value),
+        serde_json::json!("tag_value")
+    );
+}
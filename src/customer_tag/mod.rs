@@ -1,132 +1,86 @@
 #[cfg(test)]
+mod generated_tests;
+#[cfg(test)]
 mod tests;
 
+use crate::excel;
+use crate::group::{Groups, Key};
+use crate::row::{self, ExcelRow};
+use crate::source;
 use crate::ImportError;
-use calamine::{open_workbook_auto, DataType, Reader};
+use calamine::{DataType, Range};
 use serde::Serialize;
-use std::collections::HashMap;
 use std::fmt::Debug;
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Serialize, ExcelRow)]
 #[serde(rename_all = "camelCase")]
 pub struct Row {
+    #[excel(header = "Kunden ID")]
     customer_id: String,
+    #[excel(header = "Tag ID")]
     tag_id: String,
+    #[excel(header = "Tag Wert")]
     tag_value: String,
 }
 
-#[derive(Debug)]
-enum Column {
-    CustomerId,
-    TagId,
-    TagValue,
-}
-
-impl Into<usize> for Column {
-    fn into(self) -> usize {
-        self as usize
+/// Sheets read unless another one is given on the command line.
+pub fn selection() -> source::Selection {
+    source::Selection {
+        sheet: source::Sheet::First,
+        all: false,
     }
 }
 
-impl Column {
-    fn usize(self) -> usize {
-        self as usize
-    }
+/// Rows of all selected sheets, errors carry the name of the sheet they occurred in.
+pub fn run<P: AsRef<std::path::Path>>(
+    path: P,
+    selection: &source::Selection,
+) -> Result<Groups<Row>, ImportError> {
+    from_sheets(source::sheets(path, selection)?, selection)
 }
 
-fn get_column_map(headers: Vec<String>) -> Result<Vec<usize>, ImportError> {
-    let mut map: Vec<Option<usize>> = vec![None; 3];
-    for (i, header) in headers.iter().enumerate() {
-        match header.to_lowercase().trim() {
-            "kunden id" => {
-                map[<Column as Into<usize>>::into(Column::CustomerId)] = Some(i);
-            }
-
-            "tag id" => {
-                map[<Column as Into<usize>>::into(Column::TagId)] = Some(i);
-            }
-
-            "tag wert" => {
-                map[<Column as Into<usize>>::into(Column::TagValue)] = Some(i);
+/// Rows of `sheets` one after the other. When every sheet of the workbook is read, sheets
+/// without the header row of [`Row`], e.g. a cover sheet, are skipped.
+pub fn from_sheets(
+    sheets: Vec<(String, Range<DataType>)>,
+    selection: &source::Selection,
+) -> Result<Groups<Row>, ImportError> {
+    let mut groups: Groups<Row> = Groups::default();
+    let mut matched = false;
+    let mut mismatch = None;
+    for (name, sheet) in sheets {
+        if selection.every_sheet() {
+            if let Err(e) = columns(&sheet) {
+                mismatch.get_or_insert(ImportError::Sheet(name, Box::new(e)));
+                continue;
             }
-
-            _ => return Err(ImportError::UnknownHeader(header.clone())),
         }
+        matched = true;
+        let rows = from_range(&sheet).map_err(|e| ImportError::Sheet(name, Box::new(e)))?;
+        groups.append(rows);
     }
 
-    for (_i, h) in map.clone().into_iter().enumerate() {
-        // todo how to get enum from index _i?
-        match h {
-            Some(_) => {}
-            None => return Err(ImportError::MissingHeader("todo".to_string())),
-        }
+    match mismatch {
+        Some(e) if !matched => Err(e),
+        _ => Ok(groups),
     }
-
-    Ok(map.into_iter().flatten().collect())
 }
 
-pub fn run<P: AsRef<std::path::Path>>(path: P) -> Result<HashMap<String, Vec<Row>>, ImportError> {
-    let mut excel = open_workbook_auto(path)?;
+/// Column of every field of [`Row`], by the header row of `sheet`.
+fn columns(sheet: &Range<DataType>) -> Result<Vec<usize>, ImportError> {
+    row::column_map::<Row>(excel::row(sheet, 0)?)
+}
 
-    // todo input
-    let header_row = 0;
+pub fn from_range(sheet: &Range<DataType>) -> Result<Groups<Row>, ImportError> {
     let data_start_row = 1;
-    let sheet_names = excel.sheet_names().to_vec();
-    let sheet_name = sheet_names.first().unwrap();
-
-    let sheet = excel
-        .worksheet_range(sheet_name)
-        .ok_or_else(|| ImportError::SheetNotFound(sheet_name.to_string()))??;
+    let column_map = columns(sheet)?;
 
-    let headers: Vec<String> = sheet
-        .rows()
-        .nth(header_row)
-        .unwrap()
-        .iter()
-        .map(|header_cell| {
-            return match header_cell.get_string() {
-                None => String::from(""),
-                Some(v) => v.to_string(),
-            };
-        })
-        .collect();
-    let column_map: Vec<usize> = get_column_map(headers)?;
-
-    let mut groups: HashMap<String, Vec<Row>> = HashMap::new();
-    for (i, row) in sheet.rows().enumerate().skip(data_start_row) {
-        let r = transform_row(&column_map, row, i)?;
-        let k = r.customer_id.clone();
-        if !groups.contains_key(&*k) {
-            groups.insert(k.clone(), Vec::new());
-        }
-        groups.get_mut(&*k).unwrap().push(r);
-        // rows.push(r);
+    let mut groups: Groups<Row> = Groups::default();
+    for (i, cells) in sheet.rows().enumerate().skip(data_start_row) {
+        let r = Row::from_cells(cells, &column_map, i)?;
+        let key = Key::new(vec![("customerId", r.customer_id.clone())]);
+        groups.push(key, r);
     }
 
     Ok(groups)
 }
-
-fn transform_row(
-    column_map: &[usize],
-    row: &[DataType],
-    _row_number: usize,
-) -> Result<Row, ImportError> {
-    let r = Row {
-        customer_id: row[column_map[Column::CustomerId.usize()]]
-            .to_string()
-            .trim()
-            .to_string(),
-
-        tag_id: row[column_map[Column::TagId.usize()]]
-            .to_string()
-            .trim()
-            .to_string(),
-
-        tag_value: row[column_map[Column::TagValue.usize()]]
-            .to_string()
-            .trim()
-            .to_string(),
-    };
-
-    Ok(r)
-}
@@ -1,8 +1,37 @@
-use crate::customer_tag::run;
+use crate::customer_tag::{from_range, from_sheets, run, selection};
+use crate::delimited;
+use crate::source::{Selection, Sheet};
+use crate::ImportError;
+use calamine::{DataType, Range};
+
+fn sheet(rows: &[[&str; 3]]) -> Range<DataType> {
+    let mut sheet = Range::new((0, 0), (rows.len() as u32 - 1, 2));
+    for (r, row) in rows.iter().enumerate() {
+        for (c, value) in row.iter().enumerate() {
+            sheet.set_value((r as u32, c as u32), DataType::String(value.to_string()));
+        }
+    }
+    sheet
+}
+
+fn workbook() -> Vec<(String, Range<DataType>)> {
+    let header = ["Kunden ID", "Tag ID", "Tag Wert"];
+    vec![
+        (
+            "Deckblatt".to_string(),
+            sheet(&[["Kundentags", "", ""], ["Stand 2024", "", ""]]),
+        ),
+        (
+            "Tags 2023".to_string(),
+            sheet(&[header, ["1", "a", "x"], ["2", "b", "y"]]),
+        ),
+        ("Tags 2024".to_string(), sheet(&[header, ["1", "c", "z"]])),
+    ]
+}
 
 #[test]
 fn test_get_column_map_success_with_ordered_columns() {
-    let result = run("var/customer_tag.xlsx");
+    let result = run("var/customer_tag.xlsx", &selection());
     assert!(result.is_ok());
 
     let result = result.unwrap();
@@ -23,3 +52,77 @@ fn test_get_column_map_success_with_ordered_columns() {
 
     assert_eq!(rows.len(), 2);
 }
+
+#[test]
+fn test_all_sheets_skip_the_cover_sheet() {
+    let every_sheet = Selection {
+        sheet: Sheet::First,
+        all: true,
+    };
+    let groups = from_sheets(workbook(), &every_sheet).unwrap();
+    assert_eq!(groups.len(), 2);
+    assert_eq!(groups.get("1").unwrap().len(), 2);
+    assert_eq!(groups.get("2").unwrap().len(), 1);
+
+    // sheets chosen by name or pattern have to match
+    let pattern = Selection {
+        sheet: Sheet::Pattern(".".to_string()),
+        all: true,
+    };
+    assert!(matches!(
+        from_sheets(workbook(), &pattern),
+        Err(ImportError::Sheet(ref name, _)) if name == "Deckblatt"
+    ));
+
+    let cover = workbook().into_iter().take(1).collect();
+    assert!(matches!(
+        from_sheets(cover, &every_sheet),
+        Err(ImportError::Sheet(ref name, _)) if name == "Deckblatt"
+    ));
+}
+
+#[test]
+fn test_output_follows_the_order_of_the_sheets() {
+    let every_sheet = Selection {
+        sheet: Sheet::First,
+        all: true,
+    };
+    let mut sheets = workbook();
+    sheets.reverse();
+    let groups = from_sheets(sheets, &every_sheet).unwrap();
+
+    let json = serde_json::to_value(&groups).unwrap();
+    assert_eq!(
+        json,
+        serde_json::json!([
+            {"key": {"customerId": "1"}, "rows": [
+                {"customerId": "1", "tagId": "c", "tagValue": "z"},
+                {"customerId": "1", "tagId": "a", "tagValue": "x"}
+            ]},
+            {"key": {"customerId": "2"}, "rows": [
+                {"customerId": "2", "tagId": "b", "tagValue": "y"}
+            ]}
+        ])
+    );
+}
+
+#[test]
+fn test_csv_text_that_looks_like_dates_and_numbers_stays_text() {
+    let sheet = delimited::parse(
+        "Kunden ID;Tag ID;Tag Wert\n00123;31.12.2023;1.234,50\n7;08:30;12,5-\n".as_bytes(),
+    )
+    .unwrap();
+    let groups = from_range(&sheet).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(&groups).unwrap(),
+        serde_json::json!([
+            {"key": {"customerId": "00123"}, "rows": [
+                {"customerId": "00123", "tagId": "31.12.2023", "tagValue": "1.234,50"}
+            ]},
+            {"key": {"customerId": "7"}, "rows": [
+                {"customerId": "7", "tagId": "08:30", "tagValue": "12,5-"}
+            ]}
+        ])
+    );
+}
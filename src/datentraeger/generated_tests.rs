@@ -0,0 +1,335 @@
+//! Generated by create-import from the definition of the importer, changes are overwritten.
+
+use super::{from_range, Row};
+use crate::row::column_map;
+use crate::ImportError;
+use calamine::{DataType, Range};
+
+/// Names and aliases of every column in the order of [`Row`].
+const NAMES: &[&[&str]] = &[
+    &["Zusatz"],
+    &["Ort"],
+    &["Provisionspreis Energie Cent/kWh"],
+    &["VK MyE"],
+    &["Energie €/Betrag"],
+    &["Grundpreis Energie €/Betrag"],
+    &["Grundpreis Energie €/Monat"],
+    &["Energie kWh"],
+    &["E-Vertrag"],
+    &["Energieabgabe Betr."],
+    &["EEffG €/Betrag"],
+    &["EEffG Cent/kWh"],
+    &["Off.Tb.Ener.Netto"],
+    &["Gebrauchsabgabe Energie"],
+    &["Entry Exit Entgelt €/Betrag"],
+    &["Entry Exit Entgelt Cent/kWh"],
+    &["Grundpreis Netz €/Betrag"],
+    &["Grundpreis Netz €/Jahr"],
+    &["Netzverbrauch Gesamt"],
+    &["Netzverbrauch HT"],
+    &["Netzverbrauch NT"],
+    &["N-Vertrag"],
+    &["Netzarb./Netzverl./Gebr.abg."],
+    &["Netzbetreiber"],
+    &["Netzleistung kW"],
+    &["Netzleistung Betrag"],
+    &["Off.Tb.Netz Netto"],
+    &["Abwicklungsbeitrag Betrag"],
+    &["Abwicklungsbeitrag kWh"],
+    &["Einzelrechnung"],
+    &["Belegdatum"],
+    &["Belegart"],
+    &["Messpreis/Mieten"],
+    &["Zählpunktbezeichnung"],
+    &["Name"],
+    &["Nettofälligkeit"],
+    &["Hausnummer"],
+    &["Preiszonentrennung €/Betrag"],
+    &["HKN €/Betrag"],
+    &["HKN Cent/kWh"],
+    &["Blindverbrauch Betrag"],
+    &["Blindverbrauch Verr."],
+    &["Straße"],
+    &["GP MyE"],
+    &["Netto Ustpf."],
+    &["Gültig ab"],
+    &["Gültig bis"],
+    &["Umsatzsteuer"],
+    &["Arbeitspreis Energie Cent/kWh"],
+    &["PLZ"],
+];
+
+fn text(value: &str) -> DataType {
+    DataType::String(value.to_string())
+}
+
+fn headers() -> Vec<DataType> {
+    NAMES.iter().map(|names| text(names[0])).collect()
+}
+
+#[test]
+fn test_every_name_and_alias_is_accepted() {
+    for (column, names) in NAMES.iter().enumerate() {
+        for name in names.iter() {
+            let mut headers = headers();
+            headers[column] = text(name);
+            let columns = column_map::<Row>(&headers).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!(columns[column], column, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_unknown_header_is_rejected() {
+    let mut headers = headers();
+    headers.push(text("Unbekannte Spalte"));
+    assert!(matches!(
+        column_map::<Row>(&headers),
+        Err(ImportError::UnknownHeader(ref header)) if header == "Unbekannte Spalte"
+    ));
+}
+
+#[test]
+fn test_fixture_row() {
+    let cells = [
+        text("addition"),
+        text("city"),
+        DataType::Float(13.35092),
+        text("contract_account"),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(1.5),
+        text("energy_contract"),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(1.5),
+        DataType::Float(1.5),
+        DataType::Float(1.5),
+        text("grid_contract"),
+        DataType::Float(13.35092),
+        text("grid_operator"),
+        DataType::Float(1.5),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        text("invoice"),
+        DataType::DateTime(45291.0),
+        text("invoice_type"),
+        DataType::Float(13.35092),
+        text("AT0020000000000000000000000012345"),
+        text("name"),
+        DataType::DateTime(45291.0),
+        text("number"),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        text("street"),
+        text("supplier_customer_id"),
+        DataType::Float(13.35092),
+        DataType::DateTime(45291.0),
+        DataType::DateTime(45291.0),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        text("zip"),
+    ];
+    let last = (cells.len() - 1) as u32;
+    let mut sheet = Range::new((0, 0), (1, last));
+    for (i, header) in headers().into_iter().enumerate() {
+        sheet.set_value((0, i as u32), header);
+    }
+    for (i, cell) in cells.into_iter().enumerate() {
+        sheet.set_value((1, i as u32), cell);
+    }
+
+    let groups = from_range(&sheet).unwrap();
+    let rows = groups.get("supplier_customer_id/invoice").unwrap();
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(
+        serde_json::json!(row.addition),
+        serde_json::json!("addition")
+    );
+    assert_eq!(serde_json::json!(row.city), serde_json::json!("city"));
+    assert_eq!(
+        serde_json::json!(row.commission_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.contract_account),
+        serde_json::json!("contract_account")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_base_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_base_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_consumption),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_contract),
+        serde_json::json!("energy_contract")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_fee),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_law_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_law_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_tranche_net_to_pay),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_usage_fee),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.entry_exit_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.entry_exit_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_base_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_base_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_consumption),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_consumption_ht),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_consumption_nt),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_contract),
+        serde_json::json!("grid_contract")
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_fee),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_operator),
+        serde_json::json!("grid_operator")
+    );
+    assert_eq!(serde_json::json!(row.grid_power), serde_json::json!(1.5));
+    assert_eq!(
+        serde_json::json!(row.grid_power_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_tranche_net_to_pay),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.handling_fee_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.handling_fee_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(serde_json::json!(row.invoice), serde_json::json!("invoice"));
+    assert_eq!(
+        serde_json::json!(row.invoice_date),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.invoice_type),
+        serde_json::json!("invoice_type")
+    );
+    assert_eq!(
+        serde_json::json!(row.meter_fee),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.meterpoint),
+        serde_json::json!("AT0020000000000000000000000012345")
+    );
+    assert_eq!(serde_json::json!(row.name), serde_json::json!("name"));
+    assert_eq!(
+        serde_json::json!(row.net_due),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(serde_json::json!(row.number), serde_json::json!("number"));
+    assert_eq!(
+        serde_json::json!(row.price_zone),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.proof_of_origin_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.proof_of_origin_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.reactive_energy_consumption_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.reactive_energy_consumption_to_pay),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(serde_json::json!(row.street), serde_json::json!("street"));
+    assert_eq!(
+        serde_json::json!(row.supplier_customer_id),
+        serde_json::json!("supplier_customer_id")
+    );
+    assert_eq!(
+        serde_json::json!(row.total_vat),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.valid_from),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.valid_to),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(serde_json::json!(row.vat), serde_json::json!("13.35092"));
+    assert_eq!(
+        serde_json::json!(row.working_price),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(serde_json::json!(row.zip), serde_json::json!("zip"));
+}
@@ -1,17 +1,13 @@
-use crate::datentraeger::run;
+use crate::datentraeger::{run, selection};
 
 #[test]
 fn test_get_column_map_success_with_ordered_columns() {
-    let result = run("var/datentraeger.xlsx");
+    let result = run("var/datentraeger.xlsx", &selection());
     assert!(result.is_ok());
 
     let result = result.unwrap();
 
-    let rows = result.get("15205123");
-
-    assert!(rows.is_some());
-
-    let rows = rows.unwrap();
+    let rows = result.rows_where("supplierCustomerId", "15205123");
 
     assert_eq!(rows.len(), 4);
 
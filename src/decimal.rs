@@ -0,0 +1,168 @@
+use crate::locale;
+use calamine::DataType;
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::iter::Sum;
+use std::ops::Add;
+
+/// Exact decimal number for amounts and prices. f64 turns `13.35092` into `13.350919999...`
+/// and sums of amounts drift by cents.
+///
+/// Stored as `mantissa / 10^scale` without trailing zeros, serialised as string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+impl Decimal {
+    fn new(mut mantissa: i128, mut scale: u32) -> Decimal {
+        while scale > 0 && mantissa % 10 == 0 {
+            mantissa /= 10;
+            scale -= 1;
+        }
+        Decimal { mantissa, scale }
+    }
+
+    /// Parses plain `-1234.56` notation, see [`locale::normalise_number`] for localised values.
+    pub fn parse(value: &str) -> Option<Decimal> {
+        let value = value.trim();
+        let (negative, digits) = match value.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, value.strip_prefix('+').unwrap_or(value)),
+        };
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        if integer.is_empty() && fraction.is_empty() {
+            return None;
+        }
+
+        let mut mantissa: i128 = 0;
+        for c in integer.chars().chain(fraction.chars()) {
+            let digit = c.to_digit(10)?;
+            mantissa = mantissa.checked_mul(10)?.checked_add(digit as i128)?;
+        }
+        if negative {
+            mantissa = -mantissa;
+        }
+        Some(Decimal::new(mantissa, fraction.len() as u32))
+    }
+
+    /// Numeric cells are taken with the shortest representation that reads back as the same
+    /// float, which is what excel shows. Text cells may use german or english separators.
+    pub fn from_excel_value(cell: &DataType) -> Result<Option<Decimal>, String> {
+        let parsed = match cell {
+            DataType::Empty => return Ok(None),
+            DataType::String(s) if s.trim().is_empty() => return Ok(None),
+            DataType::Int(i) => Some(Decimal::new(*i as i128, 0)),
+            DataType::Float(f) if f.is_finite() => Decimal::parse(&f.to_string()),
+            DataType::String(s) => locale::normalise_number(s, locale::text_decimal(s))
+                .and_then(|n| Decimal::parse(&n)),
+            _ => None,
+        };
+
+        parsed
+            .map(Some)
+            .ok_or_else(|| format!("Could not read '{}' as decimal", cell))
+    }
+
+    fn rescale(&self, scale: u32) -> Option<i128> {
+        10i128
+            .checked_pow(scale - self.scale)?
+            .checked_mul(self.mantissa)
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    /// Panics on overflow like integer addition in debug builds.
+    fn add(self, other: Decimal) -> Decimal {
+        let scale = self.scale.max(other.scale);
+        let sum = self
+            .rescale(scale)
+            .zip(other.rescale(scale))
+            .and_then(|(a, b)| a.checked_add(b))
+            .expect("decimal overflow");
+        Decimal::new(sum, scale)
+    }
+}
+
+impl Sum for Decimal {
+    fn sum<I: Iterator<Item = Decimal>>(iter: I) -> Decimal {
+        iter.fold(Decimal::default(), |a, b| a + b)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.mantissa.unsigned_abs().to_string();
+        let sign = if self.mantissa < 0 { "-" } else { "" };
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (integer, fraction) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, integer, fraction)
+    }
+}
+
+impl Serialize for Decimal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::decimal::Decimal;
+    use calamine::DataType;
+
+    fn decimal(value: &str) -> Decimal {
+        Decimal::parse(value).unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!(decimal("13.35092").to_string(), "13.35092");
+        assert_eq!(decimal("-0.050").to_string(), "-0.05");
+        assert_eq!(decimal("+12").to_string(), "12");
+        assert_eq!(decimal(".5").to_string(), "0.5");
+        assert_eq!(decimal("1.50"), decimal("1.5"));
+        assert_eq!(Decimal::parse("1.2.3"), None);
+        assert_eq!(Decimal::parse("-"), None);
+        assert_eq!(Decimal::parse("1e5"), None);
+    }
+
+    #[test]
+    fn test_from_excel_value() {
+        assert_eq!(
+            Decimal::from_excel_value(&DataType::Float(13.35092)),
+            Ok(Some(decimal("13.35092")))
+        );
+        assert_eq!(
+            Decimal::from_excel_value(&DataType::String("1.234,56".to_string())),
+            Ok(Some(decimal("1234.56")))
+        );
+        assert_eq!(
+            Decimal::from_excel_value(&DataType::String("12,30-".to_string())),
+            Ok(Some(decimal("-12.3")))
+        );
+        assert_eq!(Decimal::from_excel_value(&DataType::Empty), Ok(None));
+        assert!(Decimal::from_excel_value(&DataType::String("n/a".to_string())).is_err());
+        assert!(Decimal::from_excel_value(&DataType::Float(f64::NAN)).is_err());
+        assert!(Decimal::from_excel_value(&DataType::Float(1e300)).is_err());
+    }
+
+    #[test]
+    fn test_sum_is_exact() {
+        let sum: Decimal = vec![decimal("0.1"); 10].into_iter().sum();
+        assert_eq!(sum, decimal("1"));
+        assert_eq!((decimal("0.1") + decimal("0.2")).to_string(), "0.3");
+        assert_eq!(
+            serde_json::to_string(&decimal("-13.35092")).unwrap(),
+            "\"-13.35092\""
+        );
+    }
+}
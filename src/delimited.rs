@@ -0,0 +1,183 @@
+use crate::locale;
+use crate::ImportError;
+use calamine::{DataType, Range};
+use chrono::{NaiveDate, NaiveDateTime};
+
+const DELIMITERS: [u8; 4] = [b';', b',', b'\t', b'|'];
+
+/// Portal and SAP exports are either UTF-8 (sometimes with BOM) or Windows-1252.
+pub fn decode(bytes: &[u8]) -> String {
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+    match std::str::from_utf8(bytes) {
+        Ok(content) => content.to_string(),
+        Err(_) => encoding_rs::WINDOWS_1252.decode(bytes).0.into_owned(),
+    }
+}
+
+/// The delimiter that splits the most lines into the same number of fields as the first line.
+pub fn detect_delimiter(content: &str) -> u8 {
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .take(20)
+        .collect();
+
+    let mut best = (0, DELIMITERS[0]);
+    for delimiter in DELIMITERS {
+        let counts: Vec<usize> = lines
+            .iter()
+            .map(|l| l.bytes().filter(|b| *b == delimiter).count())
+            .collect();
+        let consistent = match counts.first() {
+            Some(first) if *first > 0 => counts.iter().filter(|c| *c == first).count(),
+            _ => 0,
+        };
+        if consistent > best.0 {
+            best = (consistent, delimiter);
+        }
+    }
+    best.1
+}
+
+/// Excel serial number of a timestamp, so that parsed dates behave like xlsx date cells.
+pub fn serial(datetime: NaiveDateTime) -> f64 {
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .expect("valid epoch");
+    (datetime - epoch).num_milliseconds() as f64 / 86_400_000.0
+}
+
+/// Cells keep their text, so text columns read `31.12.2023` or `1.234,50` as they are written.
+/// Dates, times and numbers are parsed from the text by the column type, see [`excel`]. Only
+/// numbers whose own separators read differently than the file's decimal separator, like `1.500`
+/// in a file with decimal dots, are converted here.
+///
+/// [`excel`]: crate::excel
+fn cell(value: &str, decimal: char) -> DataType {
+    let value = value.trim();
+    if value.is_empty() {
+        return DataType::Empty;
+    }
+    match locale::parse_number(value, decimal) {
+        Some(number)
+            if locale::parse_date(value).is_none()
+                && locale::parse_text_number(value) != Some(number) =>
+        {
+            DataType::Float(number)
+        }
+        _ => DataType::String(value.to_string()),
+    }
+}
+
+/// Reads delimited text into a range of cells, see [`cell`] for what is converted.
+pub fn parse(bytes: &[u8]) -> Result<Range<DataType>, ImportError> {
+    let content = decode(bytes);
+    let delimiter = detect_delimiter(&content);
+
+    let mut rows: Vec<Vec<String>> = vec![];
+    let mut reader = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    for record in reader.records() {
+        let record = record.map_err(|e| ImportError::Error(e.to_string()))?;
+        rows.push(record.iter().map(|field| field.to_string()).collect());
+    }
+
+    let decimal = if delimiter == b',' {
+        '.'
+    } else {
+        locale::detect_decimal(rows.iter().flatten().map(|field| field.as_str()))
+    };
+
+    let width = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    if rows.is_empty() || width == 0 {
+        return Ok(Range::empty());
+    }
+
+    let mut range = Range::new((0, 0), (rows.len() as u32 - 1, width as u32 - 1));
+    for (i, row) in rows.iter().enumerate() {
+        for (j, value) in row.iter().enumerate() {
+            range.set_value((i as u32, j as u32), cell(value, decimal));
+        }
+    }
+
+    Ok(range)
+}
+
+pub fn is_delimited<P: AsRef<std::path::Path>>(path: P) -> bool {
+    let extension = path
+        .as_ref()
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase());
+    matches!(extension.as_deref(), Some("csv" | "txt" | "tsv"))
+}
+
+pub fn read<P: AsRef<std::path::Path>>(path: P) -> Result<Range<DataType>, ImportError> {
+    parse(&std::fs::read(path).map_err(ImportError::IoError)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::delimited::{decode, detect_delimiter, parse};
+    use crate::excel;
+    use calamine::DataType;
+    use chrono::{NaiveDate, NaiveTime};
+
+    #[test]
+    fn test_decode_windows_1252() {
+        assert_eq!(decode(b"Z\xE4hlpunkt;\x80"), "Zählpunkt;€");
+        assert_eq!(decode("\u{feff}Zählpunkt".as_bytes()), "Zählpunkt");
+    }
+
+    #[test]
+    fn test_detect_delimiter() {
+        assert_eq!(detect_delimiter("a;b;c\n1,5;2,5;3\n"), b';');
+        assert_eq!(detect_delimiter("a,b,c\n1.5,2.5,3\n"), b',');
+        assert_eq!(detect_delimiter("a\tb\n1,5\t2\n"), b'\t');
+    }
+
+    #[test]
+    fn test_parse_keeps_text() {
+        let range =
+            parse(b"Datum;Wert;Kunde\n01.01.2021 00:15;1.234,5;00123\n;Summe;08:30\n").unwrap();
+
+        let text = |value: &str| Some(DataType::String(value.to_string()));
+        assert_eq!(range.get_value((1, 0)).cloned(), text("01.01.2021 00:15"));
+        assert_eq!(
+            range.get_value((1, 0)).and_then(excel::as_datetime),
+            NaiveDate::from_ymd_opt(2021, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 15, 0)
+        );
+        assert_eq!(range.get_value((1, 1)).cloned(), text("1.234,5"));
+        assert_eq!(
+            range.get_value((1, 1)).and_then(excel::as_float),
+            Some(1234.5)
+        );
+        assert_eq!(range.get_value((1, 2)).cloned(), text("00123"));
+        assert_eq!(range.get_value((2, 0)), Some(&DataType::Empty));
+        assert_eq!(
+            range.get_value((2, 2)).and_then(excel::as_time),
+            NaiveTime::from_hms_opt(8, 30, 0)
+        );
+        assert_eq!(range.get_value((2, 1)).cloned(), text("Summe"));
+    }
+
+    #[test]
+    fn test_parse_converts_numbers_of_the_file_decimal() {
+        let range = parse(b"Wert,Datum\n1.500,31.12.2023\n0.25,01.01.2024\n").unwrap();
+
+        // `1.500` alone would read as german thousands
+        assert_eq!(range.get_value((1, 0)), Some(&DataType::Float(1.5)));
+        assert_eq!(
+            range.get_value((1, 1)),
+            Some(&DataType::String("31.12.2023".to_string()))
+        );
+        assert_eq!(
+            range.get_value((2, 0)),
+            Some(&DataType::String("0.25".to_string()))
+        );
+    }
+}
@@ -0,0 +1,215 @@
+use crate::locale;
+use crate::{ImportError, Reason};
+use calamine::{DataType, Range};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+
+/// Row `index` (0 based) of the sheet, an error if the sheet is shorter.
+pub fn row(sheet: &Range<DataType>, index: usize) -> Result<&[DataType], ImportError> {
+    sheet
+        .rows()
+        .nth(index)
+        .ok_or_else(|| ImportError::Error(format!("Sheet has no row {}", index + 1)))
+}
+
+/// Cell `index` of a row, cells beyond the end of the row are empty.
+pub fn cell(row: &[DataType], index: usize) -> &DataType {
+    row.get(index).unwrap_or(&DataType::Empty)
+}
+
+/// Cells of a row starting at `index`, empty if the row is shorter.
+pub fn cells_from(row: &[DataType], index: usize) -> &[DataType] {
+    row.get(index..).unwrap_or(&[])
+}
+
+/// Serial number of the day after 9999-12-31, the last date excel can represent.
+const MAX_SERIAL: f64 = 2958466.0;
+
+/// Like `DataType::as_datetime`, but serial numbers outside of the excel date range are `None`
+/// instead of overflowing and fractions are rounded to the nearest millisecond. Text cells are
+/// read as german or iso dates, times or serial numbers.
+pub fn as_datetime(cell: &DataType) -> Option<NaiveDateTime> {
+    let serial = match cell {
+        DataType::Int(i) => *i as f64,
+        DataType::Float(f) | DataType::DateTime(f) => *f,
+        DataType::String(s) => return text_datetime(s),
+        _ => return None,
+    };
+    if !(0.0..MAX_SERIAL).contains(&serial) {
+        return None;
+    }
+
+    let epoch = NaiveDate::from_ymd_opt(1899, 12, 30)?.and_hms_opt(0, 0, 0)?;
+    Some(epoch + Duration::milliseconds((serial * 86_400_000.0).round() as i64))
+}
+
+fn text_datetime(value: &str) -> Option<NaiveDateTime> {
+    if let Some(datetime) = locale::parse_datetime(value) {
+        return Some(datetime);
+    }
+    if let Some(time) = locale::parse_time(value) {
+        return NaiveDate::from_ymd_opt(1899, 12, 30).map(|epoch| epoch.and_time(time));
+    }
+    as_datetime(&DataType::Float(locale::parse_text_number(value)?))
+}
+
+pub fn as_date(cell: &DataType) -> Option<NaiveDate> {
+    as_datetime(cell).map(|dt| dt.date())
+}
+
+pub fn as_time(cell: &DataType) -> Option<NaiveTime> {
+    as_datetime(cell).map(|dt| dt.time())
+}
+
+/// Numeric cells and numbers typed into text cells, like `1.234,50` or SAP's `12,30-`.
+pub fn as_float(cell: &DataType) -> Option<f64> {
+    match cell {
+        DataType::Float(f) => Some(*f),
+        DataType::Int(i) => Some(*i as f64),
+        DataType::String(s) => locale::parse_text_number(s),
+        _ => None,
+    }
+}
+
+/// Whole numbers, `2.0` is read as `2` but `2.5` is no integer.
+pub fn as_int(cell: &DataType) -> Option<i64> {
+    let value = match cell {
+        DataType::Int(i) => return Some(*i),
+        DataType::Float(f) => *f,
+        DataType::String(s) => match s.trim().parse() {
+            Ok(i) => return Some(i),
+            Err(_) => locale::parse_text_number(s)?,
+        },
+        _ => return None,
+    };
+    if value.fract() == 0.0 && value.abs() < i64::MAX as f64 {
+        Some(value as i64)
+    } else {
+        None
+    }
+}
+
+/// Boolean cells, 0 and 1, and the usual words for yes and no, `X` marks a flag as set.
+pub fn as_bool(cell: &DataType) -> Option<bool> {
+    match cell {
+        DataType::Bool(b) => Some(*b),
+        DataType::Int(0) => Some(false),
+        DataType::Int(1) => Some(true),
+        DataType::Float(f) if *f == 0.0 || *f == 1.0 => Some(*f == 1.0),
+        DataType::String(s) => match s.trim().to_lowercase().as_str() {
+            "ja" | "j" | "yes" | "y" | "x" | "wahr" | "true" | "1" => Some(true),
+            "nein" | "n" | "no" | "falsch" | "false" | "0" => Some(false),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn type_name(cell: &DataType) -> &'static str {
+    match cell {
+        DataType::Int(_) | DataType::Float(_) => "number",
+        DataType::String(_) => "text",
+        DataType::Bool(_) => "boolean",
+        DataType::DateTime(_) => "date",
+        DataType::Error(_) => "error",
+        DataType::Empty => "empty cell",
+    }
+}
+
+/// Why `cell` could not be read as `expected`.
+pub fn reason(cell: &DataType, expected: &'static str) -> Reason {
+    match cell {
+        DataType::Empty => Reason::Empty,
+        DataType::String(s) if s.trim().is_empty() => Reason::Empty,
+        DataType::String(_) => Reason::ParseFailure(format!("could not read text as {}", expected)),
+        _ => Reason::WrongType {
+            expected,
+            found: type_name(cell),
+        },
+    }
+}
+
+pub fn value_error(
+    row_number: usize,
+    column: &str,
+    cell: &DataType,
+    reason: Reason,
+) -> ImportError {
+    ImportError::ValueError(row_number, column.to_string(), cell.to_string(), reason)
+}
+
+pub fn empty(row_number: usize, column: &str) -> ImportError {
+    ImportError::ValueError(row_number, column.to_string(), String::new(), Reason::Empty)
+}
+
+/// Reads a cell that has to have a value, e.g. with [`as_float`] or [`as_date`].
+pub fn required<T>(
+    row_number: usize,
+    column: &str,
+    cell: &DataType,
+    expected: &'static str,
+    read: impl Fn(&DataType) -> Option<T>,
+) -> Result<T, ImportError> {
+    read(cell).ok_or_else(|| value_error(row_number, column, cell, reason(cell, expected)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::excel::{as_bool, as_date, as_float, as_int, as_time, reason, required};
+    use crate::{ImportError, Reason};
+    use calamine::DataType;
+    use chrono::{NaiveDate, NaiveTime};
+
+    fn text(value: &str) -> DataType {
+        DataType::String(value.to_string())
+    }
+
+    #[test]
+    fn test_text_cells() {
+        let date = NaiveDate::from_ymd_opt(2023, 12, 31);
+        assert_eq!(as_date(&text("31.12.2023")), date);
+        assert_eq!(as_date(&text("2023-12-31")), date);
+        assert_eq!(as_date(&text("45291")), date);
+        assert_eq!(as_date(&DataType::Float(45291.0)), date);
+        assert_eq!(as_date(&text("Summe")), None);
+        assert_eq!(as_date(&text("99999999")), None);
+        assert_eq!(as_time(&text("08:30")), NaiveTime::from_hms_opt(8, 30, 0));
+
+        assert_eq!(as_float(&text("1.234,50")), Some(1234.5));
+        assert_eq!(as_float(&text("12,30-")), Some(-12.3));
+        assert_eq!(as_float(&DataType::Int(3)), Some(3.0));
+        assert_eq!(as_float(&text("n/a")), None);
+        assert_eq!(as_float(&DataType::Bool(true)), None);
+    }
+
+    #[test]
+    fn test_int_and_bool_cells() {
+        assert_eq!(as_int(&DataType::Float(2.0)), Some(2));
+        assert_eq!(as_int(&DataType::Float(2.5)), None);
+        assert_eq!(as_int(&text(" 42 ")), Some(42));
+        assert_eq!(as_int(&text("1.234")), Some(1234));
+        assert_eq!(as_int(&text("1,5")), None);
+
+        assert_eq!(as_bool(&text("X")), Some(true));
+        assert_eq!(as_bool(&text("Nein")), Some(false));
+        assert_eq!(as_bool(&DataType::Int(1)), Some(true));
+        assert_eq!(as_bool(&DataType::Bool(false)), Some(false));
+        assert_eq!(as_bool(&text("vielleicht")), None);
+    }
+
+    #[test]
+    fn test_reason() {
+        assert_eq!(reason(&DataType::Empty, "number"), Reason::Empty);
+        assert_eq!(reason(&text("  "), "number"), Reason::Empty);
+        assert_eq!(
+            reason(&DataType::Bool(true), "date"),
+            Reason::WrongType {
+                expected: "date",
+                found: "boolean"
+            }
+        );
+        assert!(matches!(
+            required(3, "Abrmenge", &text("1,2,3"), "number", as_float),
+            Err(ImportError::ValueError(3, _, ref value, Reason::ParseFailure(_))) if value == "1,2,3"
+        ));
+    }
+}
@@ -0,0 +1,196 @@
+//! Feeds random sheets through every importer. Malformed input has to end in an `ImportError`,
+//! never in a panic.
+
+use crate::{commission, contact_attempt, customer_tag, datentraeger, kam, meterpoint_value, sap};
+use calamine::{CellErrorType, DataType, Range};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+const ITERATIONS: u64 = 300;
+
+const STRINGS: [&str; 16] = [
+    "",
+    " ",
+    "Summe",
+    "Management Fee",
+    "9999-12-31",
+    "01.01.2023 00:15",
+    "1.234,56",
+    "12,5-",
+    "ÄÖÜäöüß€",
+    "Zählpunkt",
+    "Einspeisung 2.8.0",
+    "AT0020000000000000000000100003400",
+    "at002000 00000 00000000000100003400 Bezug",
+    "AT00200",
+    "ATÄ020000000000000000000100003400",
+    "\u{1F600}\u{1F600}\u{1F600}\u{1F600}\u{1F600}\u{1F600}\u{1F600}\u{1F600}\u{1F600}",
+];
+
+type Parse = fn(&Range<DataType>) -> bool;
+
+/// xorshift64, seeded so that a failing sheet can be reproduced.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+
+    fn float(&mut self) -> f64 {
+        match self.below(6) {
+            0 => f64::NAN,
+            1 => f64::INFINITY,
+            2 => -1e300,
+            3 => 1e300,
+            _ => self.next() as f64 / u64::MAX as f64 * 100_000.0 - 10_000.0,
+        }
+    }
+
+    fn cell(&mut self, strings: &[String]) -> DataType {
+        match self.below(9) {
+            0 => DataType::Empty,
+            1 => DataType::String(STRINGS[self.below(STRINGS.len())].to_string()),
+            2 if !strings.is_empty() => {
+                DataType::String(strings[self.below(strings.len())].clone())
+            }
+            3 => DataType::Float(self.float()),
+            4 => DataType::Int(self.next() as i64),
+            5 => DataType::Int(self.below(100_000) as i64),
+            6 => DataType::DateTime(self.float()),
+            7 => DataType::Bool(self.below(2) == 0),
+            _ => DataType::Error(CellErrorType::Value),
+        }
+    }
+}
+
+/// Random sheet around `header_row`. Every other sheet gets the expected headers in random order
+/// so that the data rows are parsed as well.
+fn random_sheet(rng: &mut Rng, headers: &[String], header_row: usize) -> Range<DataType> {
+    let height = rng.below(header_row + 8);
+    let width = rng.below(headers.len() + 4);
+    if height == 0 || width == 0 {
+        return Range::empty();
+    }
+
+    let mut sheet = Range::new((0, 0), (height as u32 - 1, width as u32 - 1));
+    for row in 0..height {
+        for column in 0..width {
+            sheet.set_value((row as u32, column as u32), rng.cell(headers));
+        }
+    }
+
+    if rng.below(2) == 0 {
+        let mut order: Vec<&String> = headers.iter().collect();
+        for i in (1..order.len()).rev() {
+            order.swap(i, rng.below(i + 1));
+        }
+        for (column, header) in order.into_iter().enumerate() {
+            sheet.set_value(
+                (header_row as u32, column as u32),
+                DataType::String(header.clone()),
+            );
+        }
+    }
+
+    sheet
+}
+
+/// Header names and 0 based header row of an importer definition in `data/`.
+fn definition(name: &str) -> (Vec<String>, usize) {
+    let path = format!("{}/data/{}.columns.json", env!("CARGO_MANIFEST_DIR"), name);
+    let file = std::fs::File::open(path).unwrap();
+    let definition: serde_json::Value = serde_json::from_reader(file).unwrap();
+
+    let mut headers = vec![];
+    for column in definition["columns"].as_object().unwrap().values() {
+        headers.push(column["name"].as_str().unwrap().to_string());
+    }
+    let header_row = definition["headerRowNumber"].as_u64().unwrap() as usize - 1;
+
+    (headers, header_row)
+}
+
+fn fuzz<T>(
+    name: &str,
+    headers: &[String],
+    header_row: usize,
+    parse: impl Fn(&Range<DataType>) -> T,
+) {
+    for seed in 1..=ITERATIONS {
+        let mut rng = Rng(seed.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let sheet = random_sheet(&mut rng, headers, header_row);
+        let result = catch_unwind(AssertUnwindSafe(|| parse(&sheet)));
+        assert!(result.is_ok(), "{} panicked for seed {}", name, seed);
+    }
+}
+
+#[test]
+fn test_importers_do_not_panic() {
+    let importers: [(&str, Parse); 6] = [
+        ("commission", |sheet| commission::from_range(sheet).is_ok()),
+        ("contact_attempt", |sheet| {
+            contact_attempt::from_range(sheet).is_ok()
+        }),
+        ("customer_tag", |sheet| {
+            customer_tag::from_range(sheet).is_ok()
+        }),
+        ("datentraeger", |sheet| {
+            datentraeger::from_range(sheet).is_ok()
+        }),
+        ("kam", |sheet| kam::from_range(sheet).is_ok()),
+        ("sap", |sheet| sap::from_range(sheet).is_ok()),
+    ];
+
+    for (name, parse) in importers {
+        let (headers, header_row) = definition(name);
+        fuzz(name, &headers, header_row, parse);
+    }
+}
+
+#[test]
+fn test_meterpoint_value_does_not_panic() {
+    // cells that make detect_schema pick each of the parsers
+    let schemas: [&[((u32, u32), &str)]; 4] = [
+        &[
+            ((1, 0), "Zeitpunkt"),
+            ((1, 1), "Abnahmestelle"),
+            ((6, 1), "Zählpunkt"),
+            ((13, 1), "Wirkverbrauch_kWh"),
+        ],
+        &[
+            ((0, 0), "Kopfdaten des Profils"),
+            ((6, 0), "Maßeinheit"),
+            ((6, 1), "kW"),
+            ((1, 3), "Ab-Datum"),
+            ((1, 4), "Ab-Zeit"),
+            ((1, 5), "Profilwert"),
+        ],
+        &[((0, 0), "Timestamp")],
+        &[((0, 0), "Werte in kW")],
+    ];
+    let headers: Vec<String> = STRINGS.iter().map(|s| s.to_string()).collect();
+
+    for markers in schemas {
+        fuzz("meterpoint_value", &headers, 0, |sheet| {
+            let mut sheet = sheet.clone();
+            if sheet.is_empty() {
+                sheet = Range::new((0, 0), (0, 0));
+            }
+            for (position, value) in markers {
+                sheet.set_value(*position, DataType::String(value.to_string()));
+            }
+            meterpoint_value::from_range(
+                sheet,
+                "Lastprofil AT0030000000000000000000000001234.xlsx".to_string(),
+            )
+            .is_ok()
+        });
+    }
+}
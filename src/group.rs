@@ -0,0 +1,171 @@
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// Values of the group key fields by their name, serialised as object like
+/// `{"supplierCustomerId": "123", "invoice": "456"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Key(Vec<(&'static str, String)>);
+
+impl Key {
+    pub fn new(parts: Vec<(&'static str, String)>) -> Key {
+        Key(parts)
+    }
+}
+
+/// The values joined by `/`, just the value for keys of one field.
+impl Display for Key {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let values: Vec<&str> = self.0.iter().map(|(_, value)| value.as_str()).collect();
+        write!(f, "{}", values.join("/"))
+    }
+}
+
+impl Serialize for Key {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (name, value) in &self.0 {
+            map.serialize_entry(name, value)?;
+        }
+        map.end()
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Group<R> {
+    pub key: Key,
+    pub rows: Vec<R>,
+}
+
+/// Rows grouped by their key, serialised as list of [`Group`]. Groups keep the order in which
+/// their first row was pushed and rows the order they were pushed in, so the output of an import
+/// follows the sheets and does not change between runs.
+#[derive(Debug)]
+pub struct Groups<R> {
+    groups: Vec<Group<R>>,
+    index: HashMap<Key, usize>,
+}
+
+impl<R> Default for Groups<R> {
+    fn default() -> Self {
+        Groups {
+            groups: vec![],
+            index: HashMap::new(),
+        }
+    }
+}
+
+impl<R> Groups<R> {
+    pub fn push(&mut self, key: Key, row: R) {
+        match self.index.get(&key) {
+            Some(&i) => self.groups[i].rows.push(row),
+            None => {
+                self.index.insert(key.clone(), self.groups.len());
+                self.groups.push(Group {
+                    key,
+                    rows: vec![row],
+                });
+            }
+        }
+    }
+
+    /// Adds the rows of `other`, e.g. of the next sheet, to the groups with the same key. New
+    /// groups come after the existing ones.
+    pub fn append(&mut self, other: Groups<R>) {
+        for group in other.groups {
+            for row in group.rows {
+                self.push(group.key.clone(), row);
+            }
+        }
+    }
+}
+
+/// Lookups for the tests of the importers.
+#[cfg(test)]
+impl<R> Groups<R> {
+    /// Rows of the group whose key is displayed as `key`, see [`Key`].
+    pub fn get(&self, key: &str) -> Option<&Vec<R>> {
+        self.groups
+            .iter()
+            .find(|group| group.key.to_string() == key)
+            .map(|group| &group.rows)
+    }
+
+    /// Rows of every group whose key field `field` is `value`, e.g. all invoices of a customer.
+    pub fn rows_where(&self, field: &str, value: &str) -> Vec<&R> {
+        self.groups
+            .iter()
+            .filter(|group| group.key.0.iter().any(|(f, v)| *f == field && v == value))
+            .flat_map(|group| &group.rows)
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+}
+
+impl<R: Serialize> Serialize for Groups<R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.groups.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::group::{Groups, Key};
+
+    fn key(customer: &str, invoice: &str) -> Key {
+        Key::new(vec![
+            ("supplierCustomerId", customer.to_string()),
+            ("invoice", invoice.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_groups() {
+        let mut groups = Groups::default();
+        groups.push(key("1", "10"), 1);
+        groups.push(key("1", "11"), 2);
+        groups.push(key("1", "10"), 3);
+
+        let mut other = Groups::default();
+        other.push(key("2", "20"), 4);
+        other.push(key("1", "11"), 5);
+        groups.append(other);
+
+        assert_eq!(groups.len(), 3);
+        assert_eq!(groups.get("1/10"), Some(&vec![1, 3]));
+        assert_eq!(groups.get("1/11"), Some(&vec![2, 5]));
+        assert_eq!(groups.get("1"), None);
+        assert_eq!(
+            groups.rows_where("supplierCustomerId", "1"),
+            vec![&1, &3, &2, &5]
+        );
+        assert_eq!(
+            serde_json::to_string(&groups).unwrap(),
+            r#"[{"key":{"supplierCustomerId":"1","invoice":"10"},"rows":[1,3]},{"key":{"supplierCustomerId":"1","invoice":"11"},"rows":[2,5]},{"key":{"supplierCustomerId":"2","invoice":"20"},"rows":[4]}]"#
+        );
+    }
+
+    #[test]
+    fn test_order_follows_the_sheets() {
+        let customer = |id: &str| Key::new(vec![("supplierCustomerId", id.to_string())]);
+        let sheet = |rows: &[(&str, u32)]| {
+            let mut groups = Groups::default();
+            for (id, row) in rows {
+                groups.push(customer(id), *row);
+            }
+            groups
+        };
+
+        let mut groups = sheet(&[("9", 1), ("3", 2), ("9", 3)]);
+        groups.append(sheet(&[("1", 4), ("3", 5), ("10", 6)]));
+
+        let keys: Vec<String> = groups.groups.iter().map(|g| g.key.to_string()).collect();
+        assert_eq!(keys, vec!["9", "3", "1", "10"]);
+        assert_eq!(groups.get("3"), Some(&vec![2, 5]));
+        assert_eq!(groups.get("9"), Some(&vec![1, 3]));
+    }
+}
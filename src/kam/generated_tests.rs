@@ -0,0 +1,256 @@
+//! Generated by create-import from the definition of the importer, changes are overwritten.
+
+use super::{from_range, Row};
+use crate::row::column_map;
+use crate::ImportError;
+use calamine::{DataType, Range};
+
+/// Names and aliases of every column in the order of [`Row`].
+const NAMES: &[&[&str]] = &[
+    &["Zugehörigkeit"],
+    &["R-Zusatz"],
+    &["R-Ort"],
+    &["R-Hausnummer"],
+    &["R-Straße"],
+    &["R-Postleitzahl", "R-PLZ"],
+    &["Abrechnungsklasse"],
+    &["Wert bei Wechsel kWh", "Jahresverbrauch bei Wechsel kWh"],
+    &["aktueller Prognosewert kWh", "Prognosewert kWh"],
+    &["Vertrag"],
+    &["Vertragskonto"],
+    &["Korresp. Empfänger", "Korrespondenzempfänger"],
+    &["Abw.Rechnungsempfg", "abw Rechnungsempfänger"],
+    &["E-Rechnung ZPDF=ja", "E-Mail Rechnung ZPDF=JA"],
+    &["Serviceart", "Sparte"],
+    &["VorleistungsmodellNetz"],
+    &["Einzugsdatum"],
+    &["Zählpunktbezeichnung", "Zählpunkt"],
+    &["Lagezusatz"],
+    &["A-Ort"],
+    &["A-Hausnummer"],
+    &["A-Straße"],
+    &["A-Postleitzahl", "A-PLZ"],
+    &["Name1"],
+    &["Name2"],
+    &["Auszugsdatum"],
+    &["Poolbetreiber-Kundennummer"],
+    &["Bez. des Profils", "Lastprofil"],
+    &["Ableseeinheit"],
+    &["SEPA hinterlegt E=JA", "Sepa"],
+    &["SEPA gesperrt", "SEPA Sperre"],
+    &["Gruppenkopf_AV", "Gruppenkopf"],
+    &["Gruppenkopf_AV_Name", "Name-Gruppenkopf"],
+    &["Geschäftspartner"],
+    &["Anlage"],
+    &["Tariftyp"],
+];
+
+fn text(value: &str) -> DataType {
+    DataType::String(value.to_string())
+}
+
+fn headers() -> Vec<DataType> {
+    NAMES.iter().map(|names| text(names[0])).collect()
+}
+
+#[test]
+fn test_every_name_and_alias_is_accepted() {
+    for (column, names) in NAMES.iter().enumerate() {
+        for name in names.iter() {
+            let mut headers = headers();
+            headers[column] = text(name);
+            let columns = column_map::<Row>(&headers).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!(columns[column], column, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_unknown_header_is_rejected() {
+    let mut headers = headers();
+    headers.push(text("Unbekannte Spalte"));
+    assert!(matches!(
+        column_map::<Row>(&headers),
+        Err(ImportError::UnknownHeader(ref header)) if header == "Unbekannte Spalte"
+    ));
+}
+
+#[test]
+fn test_fixture_row() {
+    let cells = [
+        text("affiliate"),
+        text("bill_addition"),
+        text("bill_city"),
+        text("bill_number"),
+        text("bill_street"),
+        text("bill_zip"),
+        text("billing_type"),
+        DataType::Float(1.5),
+        DataType::Float(1.5),
+        text("contract"),
+        text("contract_account"),
+        text("corresponding_bill_receiver"),
+        text("deviant_bill_receiver"),
+        text("ZPDF"),
+        text("energy_type"),
+        text("grid_billing_integrated"),
+        DataType::DateTime(45291.0),
+        text("AT0020000000000000000000000012345"),
+        text("mp_addition"),
+        text("mp_city"),
+        text("mp_number"),
+        text("mp_street"),
+        text("mp_zip"),
+        text("name"),
+        text("name_add"),
+        DataType::DateTime(45291.0),
+        text("pool_customer_id"),
+        text("profile"),
+        text("read_unit"),
+        text("E"),
+        text("X"),
+        text("supplier_customer_group_id"),
+        text("supplier_customer_group_name"),
+        text("supplier_customer_id"),
+        text("supplier_meterpoint_id"),
+        text("tariff_typ"),
+    ];
+    let last = (cells.len() - 1) as u32;
+    let mut sheet = Range::new((0, 0), (1, last));
+    for (i, header) in headers().into_iter().enumerate() {
+        sheet.set_value((0, i as u32), header);
+    }
+    for (i, cell) in cells.into_iter().enumerate() {
+        sheet.set_value((1, i as u32), cell);
+    }
+
+    let groups = from_range(&sheet).unwrap();
+    let rows = groups
+        .get("supplier_customer_id/AT0020000000000000000000000012345")
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(
+        serde_json::json!(row.affiliate),
+        serde_json::json!("affiliate")
+    );
+    assert_eq!(
+        serde_json::json!(row.bill_addition),
+        serde_json::json!("bill_addition")
+    );
+    assert_eq!(
+        serde_json::json!(row.bill_city),
+        serde_json::json!("bill_city")
+    );
+    assert_eq!(
+        serde_json::json!(row.bill_number),
+        serde_json::json!("bill_number")
+    );
+    assert_eq!(
+        serde_json::json!(row.bill_street),
+        serde_json::json!("bill_street")
+    );
+    assert_eq!(
+        serde_json::json!(row.bill_zip),
+        serde_json::json!("bill_zip")
+    );
+    assert_eq!(
+        serde_json::json!(row.billing_type),
+        serde_json::json!("billing_type")
+    );
+    assert_eq!(
+        serde_json::json!(row.consumption_at_change),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.consumption_forecast),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.contract),
+        serde_json::json!("contract")
+    );
+    assert_eq!(
+        serde_json::json!(row.contract_account),
+        serde_json::json!("contract_account")
+    );
+    assert_eq!(
+        serde_json::json!(row.corresponding_bill_receiver),
+        serde_json::json!("corresponding_bill_receiver")
+    );
+    assert_eq!(
+        serde_json::json!(row.deviant_bill_receiver),
+        serde_json::json!("deviant_bill_receiver")
+    );
+    assert_eq!(serde_json::json!(row.e_invoice), serde_json::json!(true));
+    assert_eq!(
+        serde_json::json!(row.energy_type),
+        serde_json::json!("energy_type")
+    );
+    assert_eq!(
+        serde_json::json!(row.grid_billing_integrated),
+        serde_json::json!("grid_billing_integrated")
+    );
+    assert_eq!(
+        serde_json::json!(row.in_date),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.meterpoint),
+        serde_json::json!("AT0020000000000000000000000012345")
+    );
+    assert_eq!(
+        serde_json::json!(row.mp_addition),
+        serde_json::json!("mp_addition")
+    );
+    assert_eq!(serde_json::json!(row.mp_city), serde_json::json!("mp_city"));
+    assert_eq!(
+        serde_json::json!(row.mp_number),
+        serde_json::json!("mp_number")
+    );
+    assert_eq!(
+        serde_json::json!(row.mp_street),
+        serde_json::json!("mp_street")
+    );
+    assert_eq!(serde_json::json!(row.mp_zip), serde_json::json!("mp_zip"));
+    assert_eq!(serde_json::json!(row.name), serde_json::json!("name"));
+    assert_eq!(
+        serde_json::json!(row.name_add),
+        serde_json::json!("name_add")
+    );
+    assert_eq!(
+        serde_json::json!(row.out_date),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.pool_customer_id),
+        serde_json::json!("pool_customer_id")
+    );
+    assert_eq!(serde_json::json!(row.profile), serde_json::json!("profile"));
+    assert_eq!(
+        serde_json::json!(row.read_unit),
+        serde_json::json!("read_unit")
+    );
+    assert_eq!(serde_json::json!(row.sepa), serde_json::json!(true));
+    assert_eq!(serde_json::json!(row.sepa_blocked), serde_json::json!(true));
+    assert_eq!(
+        serde_json::json!(row.supplier_customer_group_id),
+        serde_json::json!("supplier_customer_group_id")
+    );
+    assert_eq!(
+        serde_json::json!(row.supplier_customer_group_name),
+        serde_json::json!("supplier_customer_group_name")
+    );
+    assert_eq!(
+        serde_json::json!(row.supplier_customer_id),
+        serde_json::json!("supplier_customer_id")
+    );
+    assert_eq!(
+        serde_json::json!(row.supplier_meterpoint_id),
+        serde_json::json!("supplier_meterpoint_id")
+    );
+    assert_eq!(
+        serde_json::json!(row.tariff_typ),
+        serde_json::json!("tariff_typ")
+    );
+}
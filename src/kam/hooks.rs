@@ -0,0 +1,9 @@
+use super::Row;
+use chrono::NaiveDate;
+
+/// SAP exports customers that have not moved out with the out date 9999-12-31.
+pub fn clear_open_out_date(r: &mut Row) {
+    if r.out_date == NaiveDate::from_ymd_opt(9999, 12, 31) {
+        r.out_date = None;
+    }
+}
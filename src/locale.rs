@@ -0,0 +1,189 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+
+// exports from german speaking systems write `1.234,56` and `31.12.2023`, everything else
+// `1,234.56` and `2023-12-31`. these helpers accept both.
+
+const DATETIME_FORMATS: [&str; 6] = [
+    "%d.%m.%Y %H:%M:%S",
+    "%d.%m.%Y %H:%M",
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%d/%m/%Y %H:%M",
+];
+
+const DATE_FORMATS: [&str; 3] = ["%d.%m.%Y", "%Y-%m-%d", "%d/%m/%Y"];
+
+const TIME_FORMATS: [&str; 2] = ["%H:%M:%S", "%H:%M"];
+
+/// Parses a number written with the given decimal separator, the other one of `.` and `,` is
+/// taken as thousands separator. SAP writes negative amounts with a trailing minus (`12,50-`).
+pub fn parse_number(value: &str, decimal: char) -> Option<f64> {
+    normalise_number(value, decimal)?.parse().ok()
+}
+
+/// The number in plain `-1234.56` notation, without rounding it through a float.
+pub fn normalise_number(value: &str, decimal: char) -> Option<String> {
+    let thousands = if decimal == ',' { '.' } else { ',' };
+    let value = value.trim();
+    let (value, sign) = match value.strip_suffix('-') {
+        Some(value) if !value.starts_with('-') => (value, "-"),
+        _ => (value, ""),
+    };
+    let value: String = sign
+        .chars()
+        .chain(value.chars())
+        .filter(|c| *c != thousands && *c != ' ' && *c != '\u{a0}')
+        .map(|c| if c == decimal { '.' } else { c })
+        .collect();
+
+    if value.is_empty() || !value.chars().any(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    if !value
+        .chars()
+        .all(|c| c.is_ascii_digit() || c == '.' || c == '-' || c == '+')
+    {
+        return None;
+    }
+    Some(value)
+}
+
+/// Guesses the decimal separator from sample values: a single comma is read as german decimal
+/// comma, a dot only counts if it is not followed by exactly three digits like in `1.500`. Dates
+/// like `05.01.2023` don't count.
+pub fn detect_decimal<'a>(values: impl Iterator<Item = &'a str>) -> char {
+    let (mut comma, mut dot) = (0, 0);
+    for value in values {
+        let value = value.trim().trim_matches('-');
+        if !value
+            .chars()
+            .all(|c| c.is_ascii_digit() || c == '.' || c == ',')
+            || !value.starts_with(|c: char| c.is_ascii_digit())
+            || parse_date(value).is_some()
+        {
+            continue;
+        }
+        match (value.rfind(','), value.rfind('.')) {
+            (Some(c), Some(d)) if c > d => comma += 1,
+            (Some(_), Some(_)) => dot += 1,
+            (Some(_), None) if value.matches(',').count() == 1 => comma += 1,
+            (None, Some(d)) if value.len() - d - 1 != 3 => dot += 1,
+            _ => {}
+        }
+    }
+
+    if comma > dot {
+        ','
+    } else {
+        '.'
+    }
+}
+
+/// Decimal separator of a single number typed into a text cell. Without a comma the german
+/// reading wins for grouped thousands like `1.234`, `13.35092` stays a decimal.
+pub fn text_decimal(value: &str) -> char {
+    let value = value.trim().trim_matches('-');
+    match (value.rfind(','), value.rfind('.')) {
+        (Some(c), Some(d)) if d > c => '.',
+        (Some(_), _) => ',',
+        (None, Some(_)) if is_grouped(value) => ',',
+        _ => '.',
+    }
+}
+
+/// `1.234` or `12.345.678`
+fn is_grouped(value: &str) -> bool {
+    let mut groups = value.split('.');
+    let first = groups.next().unwrap_or_default();
+    !first.is_empty()
+        && first.len() <= 3
+        && !first.starts_with('0')
+        && first.chars().all(|c| c.is_ascii_digit())
+        && groups.all(|g| g.len() == 3 && g.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// A number typed into a text cell, see [`text_decimal`].
+pub fn parse_text_number(value: &str) -> Option<f64> {
+    parse_number(value, text_decimal(value))
+}
+
+pub fn parse_datetime(value: &str) -> Option<NaiveDateTime> {
+    let value = value.trim();
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| parse_date(value).and_then(|d| d.and_hms_opt(0, 0, 0)))
+}
+
+pub fn parse_date(value: &str) -> Option<NaiveDate> {
+    let value = value.trim();
+    DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(value, format).ok())
+}
+
+pub fn parse_time(value: &str) -> Option<NaiveTime> {
+    let value = value.trim();
+    TIME_FORMATS
+        .iter()
+        .find_map(|format| NaiveTime::parse_from_str(value, format).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::locale::{detect_decimal, parse_datetime, parse_number, parse_text_number};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_parse_number() {
+        assert_eq!(parse_number("1.234,56", ','), Some(1234.56));
+        assert_eq!(parse_number("-0,5", ','), Some(-0.5));
+        assert_eq!(parse_number("1,234.56", '.'), Some(1234.56));
+        assert_eq!(parse_number(" 12 ", '.'), Some(12.0));
+        assert_eq!(parse_number("1.234,50-", ','), Some(-1234.5));
+        assert_eq!(parse_number("-", ','), None);
+        assert_eq!(parse_number("", ','), None);
+        assert_eq!(parse_number("AT1", ','), None);
+    }
+
+    #[test]
+    fn test_detect_decimal() {
+        assert_eq!(detect_decimal(["6,870", "1.234,5", "12"].into_iter()), ',');
+        assert_eq!(detect_decimal(["6.87", "1,234.5", "x"].into_iter()), '.');
+        // thousands separators alone are no evidence
+        assert_eq!(detect_decimal(["1.500", "2.000"].into_iter()), '.');
+        assert_eq!(
+            detect_decimal(["05.01.2023", "1,623", "06.01.2023"].into_iter()),
+            ','
+        );
+    }
+
+    #[test]
+    fn test_parse_text_number() {
+        assert_eq!(parse_text_number("1.234,50"), Some(1234.5));
+        assert_eq!(parse_text_number("1.234"), Some(1234.0));
+        assert_eq!(parse_text_number("12.345.678"), Some(12345678.0));
+        assert_eq!(parse_text_number("13.35092"), Some(13.35092));
+        assert_eq!(parse_text_number("0.500"), Some(0.5));
+        assert_eq!(parse_text_number("1,234.5"), Some(1234.5));
+        assert_eq!(parse_text_number("12,30-"), Some(-12.3));
+        assert_eq!(parse_text_number("31.12.2023"), None);
+    }
+
+    #[test]
+    fn test_parse_datetime() {
+        let expected = NaiveDate::from_ymd_opt(2021, 1, 1)
+            .unwrap()
+            .and_hms_opt(0, 15, 0);
+        assert_eq!(parse_datetime("01.01.2021 00:15"), expected);
+        assert_eq!(parse_datetime("2021-01-01T00:15:00"), expected);
+        assert_eq!(
+            parse_datetime("01.01.2021"),
+            NaiveDate::from_ymd_opt(2021, 1, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+        );
+        assert_eq!(parse_datetime("Summe"), None);
+    }
+}
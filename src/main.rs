@@ -2,14 +2,25 @@ mod commission;
 mod contact_attempt;
 mod customer_tag;
 mod datentraeger;
+mod decimal;
+mod delimited;
+mod excel;
+#[cfg(test)]
+mod fuzz;
+mod group;
 mod kam;
+mod locale;
+mod meterpoint;
 mod meterpoint_value;
+mod row;
 mod sap;
+mod source;
 
 use chrono::Utc;
+use group::Groups;
 use regex::Regex;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::env;
 use std::fmt::{Debug, Display, Formatter};
 use std::fs::File;
@@ -21,17 +32,45 @@ pub enum ImportError {
     Serialize(serde_json::error::Error),
     UnknownImport(String),
     SheetNotFound(String),
-    ValueError(usize, String, String),
+    Sheet(String, Box<ImportError>),
+    /// row, column, raw cell value and why it could not be read
+    ValueError(usize, String, String, Reason),
     UnknownHeader(String),
     MissingHeader(String),
+    InvalidProfile(String),
     IoError(std::io::Error),
     Error(String),
 }
 
+/// Why a cell could not be read, tells users whether to fill a blank or to fix a typo.
+#[derive(Debug, PartialEq)]
+pub enum Reason {
+    Empty,
+    WrongType {
+        expected: &'static str,
+        found: &'static str,
+    },
+    ParseFailure(String),
+    UnknownEnumValue,
+}
+
+impl Display for Reason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Reason::Empty => write!(f, "cell has no value"),
+            Reason::WrongType { expected, found } => {
+                write!(f, "expected {} but found {}", expected, found)
+            }
+            Reason::ParseFailure(message) => write!(f, "{}", message),
+            Reason::UnknownEnumValue => write!(f, "unknown value"),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct Schema<T: Serialize> {
     messages: T,
-    meta: HashMap<String, String>,
+    meta: BTreeMap<String, String>,
 }
 
 impl Display for ImportError {
@@ -43,8 +82,13 @@ impl Display for ImportError {
             ImportError::SheetNotFound(ref name) => {
                 write!(f, "Could not find sheet with name {}", name)
             }
-            ImportError::ValueError(ref i, ref name, ref message) => {
-                write!(f, "Value '{}' at row {} column {}", message, i, name)
+            ImportError::Sheet(ref name, ref err) => write!(f, "{} in sheet {}", err, name),
+            ImportError::ValueError(ref i, ref name, ref value, ref reason) => {
+                write!(
+                    f,
+                    "Value '{}' at row {} column {}: {}",
+                    value, i, name, reason
+                )
             }
             ImportError::UnknownHeader(ref header) => {
                 write!(f, "Unknown header name {} found", header)
@@ -52,6 +96,9 @@ impl Display for ImportError {
             ImportError::MissingHeader(ref header) => {
                 write!(f, "Missing header name {}", header)
             }
+            ImportError::InvalidProfile(ref report) => {
+                write!(f, "Load profile failed validation: {}", report)
+            }
             ImportError::Error(ref err) => {
                 write!(f, "{}", err)
             }
@@ -67,10 +114,28 @@ impl From<calamine::Error> for ImportError {
 }
 
 fn main() {
-    let excel_type = env::args().nth(1).unwrap();
-    let path = env::args().nth(2).unwrap();
+    let (flags, args): (Vec<String>, Vec<String>) =
+        env::args().skip(1).partition(|arg| arg.starts_with("--"));
+
+    if args.len() < 2 {
+        println!(
+            "Usage: importsctl <type> <path> [--sheet=<name>|--sheet-pattern=<regex>] [--all-sheets] [--strict] [--fill=auto|linear|profile|zero] [--max-gap=<intervals>] [--resample=hour|day|month] [--aggregate=sum|mean|max]"
+        );
+        println!("       importsctl merge-profiles <path>... [--sheet=<name>|--sheet-pattern=<regex>] [--all-sheets] [--overlap=newer|error|average] [--output=<path>]");
+        println!(
+            "       (files are applied in the given order, with --overlap=newer later files win)"
+        );
+        std::process::exit(1);
+    }
+    let excel_type = args[0].clone();
+    let path = args[1].clone();
+
+    let result = match excel_type.as_str() {
+        "merge-profiles" => merge_profiles(&args[1..], &flags),
+        _ => run(excel_type, path, &flags),
+    };
 
-    match run(excel_type, path) {
+    match result {
         Ok(_) => {}
         Err(err) => {
             println!("{}", err);
@@ -79,29 +144,122 @@ fn main() {
     }
 }
 
-fn run(excel_type: String, path: String) -> Result<(), ImportError> {
+fn flag_value<'a>(flags: &'a [String], name: &str) -> Option<&'a str> {
+    flags
+        .iter()
+        .find_map(|flag| flag.strip_prefix(name)?.strip_prefix('='))
+}
+
+/// `--sheet=<name>` and `--sheet-pattern=<regex>` replace the sheet of the import definition,
+/// `--all-sheets` concatenates every matching sheet, without a name or pattern every sheet with
+/// the header row of the importer.
+fn sheet_selection(default: source::Selection, flags: &[String]) -> source::Selection {
+    let sheet = match (
+        flag_value(flags, "--sheet"),
+        flag_value(flags, "--sheet-pattern"),
+    ) {
+        (Some(name), _) => source::Sheet::Name(name.to_string()),
+        (None, Some(pattern)) => source::Sheet::Pattern(pattern.to_string()),
+        (None, None) => default.sheet,
+    };
+
+    source::Selection {
+        sheet,
+        all: default.all || flags.iter().any(|flag| flag == "--all-sheets"),
+    }
+}
+
+fn write_profile(
+    json_writer: &File,
+    rows: meterpoint_value::Data,
+    mut meta: BTreeMap<String, String>,
+    flags: &[String],
+) -> Result<(), ImportError> {
+    let report = meterpoint_value::validate(&rows);
+    if flags.iter().any(|flag| flag == "--strict") && !report.is_valid() {
+        return Err(ImportError::InvalidProfile(report.summary()));
+    }
+    report.write_meta(&mut meta);
+    rows.write_meta(&mut meta);
+
+    let rows = match flag_value(flags, "--fill") {
+        Some(strategy) => {
+            let max_gap = match flag_value(flags, "--max-gap") {
+                Some(max_gap) => max_gap
+                    .parse()
+                    .map_err(|_| ImportError::Error(format!("Invalid max gap {}", max_gap)))?,
+                None => 4,
+            };
+            meterpoint_value::fill(&rows, strategy.parse()?, max_gap)
+        }
+        None => rows,
+    };
+
+    let rows = match flag_value(flags, "--resample") {
+        Some(resolution) => {
+            let aggregation = flag_value(flags, "--aggregate").unwrap_or("sum");
+            meterpoint_value::resample(&rows, resolution.parse()?, aggregation.parse()?)
+        }
+        None => rows,
+    };
+    let s = Schema {
+        messages: vec![rows],
+        meta,
+    };
+    serde_json::to_writer(json_writer, &s).map_err(ImportError::Serialize)
+}
+
+fn merge_profiles(paths: &[String], flags: &[String]) -> Result<(), ImportError> {
+    let json_path = match flag_value(flags, "--output") {
+        Some(output) => Path::new(output).to_path_buf(),
+        None => Path::new(paths[0].as_str()).with_extension("merged.json"),
+    };
+    let overlap = flag_value(flags, "--overlap").unwrap_or("newer").parse()?;
+
+    // files are applied in the order they are given, later files win on overlapping intervals
+    let selection = sheet_selection(meterpoint_value::selection(), flags);
+    let mut profiles: Vec<meterpoint_value::Data> = Vec::new();
+    for path in paths {
+        profiles.push(meterpoint_value::run(path.to_string(), &selection)?);
+    }
+    let rows = meterpoint_value::merge(profiles, overlap)?;
+
+    let json_writer = &File::create(json_path).map_err(ImportError::IoError)?;
+    let mut meta: BTreeMap<String, String> = BTreeMap::new();
+    meta.insert("created_at".to_string(), Utc::now().to_string());
+    meta.insert("sources".to_string(), paths.join(","));
+
+    write_profile(json_writer, rows, meta, flags)
+}
+
+fn run(excel_type: String, path: String, flags: &[String]) -> Result<(), ImportError> {
     let json_path = Path::new(path.clone().as_str()).with_extension("json");
 
-    let json_writer = &File::create(json_path).map_err(|e| ImportError::IoError(e))?;
+    let json_writer = &File::create(json_path).map_err(ImportError::IoError)?;
 
     match excel_type.as_str() {
         "mye_datentraeger" => {
-            let rows = datentraeger::run(path)?;
-            let mut meta: HashMap<String, String> = HashMap::new();
+            let rows = datentraeger::run(path, &sheet_selection(datentraeger::selection(), flags))?;
+            let mut meta: BTreeMap<String, String> = BTreeMap::new();
             meta.insert("created_at".to_string(), Utc::now().to_string());
-            let s: Schema<Vec<&Vec<datentraeger::Row>>> = Schema {
-                messages: rows.values().collect(),
+            let s: Schema<&Groups<datentraeger::Row>> = Schema {
+                messages: &rows,
                 meta,
             };
-            serde_json::to_writer(json_writer, &s).map_err(|err| ImportError::Serialize(err))?;
+            serde_json::to_writer(json_writer, &s).map_err(ImportError::Serialize)?;
         }
         "mye_commission" => {
-            let rows = commission::run(path.clone())?;
+            let rows = commission::run(
+                path.clone(),
+                &sheet_selection(commission::selection(), flags),
+            )?;
 
-            let mut meta: HashMap<String, String> = HashMap::new();
+            let mut meta: BTreeMap<String, String> = BTreeMap::new();
             meta.insert("created_at".to_string(), Utc::now().to_string());
 
-            let re = Regex::new(r".*commissions-enelteco-(?P<timeframe>[0-9]{4}-[0-9]{2})\.xlsx?")
+            let re = Regex::new(
+                r"(?i).*commissions-enelteco-(?P<timeframe>[0-9]{4}-[0-9]{2})\.(xlsx?|ods|csv|tsv|txt)$",
+            )
                 .unwrap();
 
             let timeframe = re.captures(path.as_str()).and_then(|cap| {
@@ -120,32 +278,34 @@ fn run(excel_type: String, path: String) -> Result<(), ImportError> {
                 }
             }
 
-            let s: Schema<Vec<&Vec<commission::Row>>> = Schema {
-                messages: rows.values().collect(),
+            let s: Schema<&Groups<commission::Row>> = Schema {
+                messages: &rows,
                 meta,
             };
 
-            serde_json::to_writer(json_writer, &s).map_err(|err| ImportError::Serialize(err))?;
+            serde_json::to_writer(json_writer, &s).map_err(ImportError::Serialize)?;
         }
         "mye_sap" => {
-            let rows = sap::run(path)?;
+            let rows = sap::run(path, &sheet_selection(sap::selection(), flags))?;
 
-            let mut meta: HashMap<String, String> = HashMap::new();
+            let mut meta: BTreeMap<String, String> = BTreeMap::new();
             meta.insert("created_at".to_string(), Utc::now().to_string());
-            let s: Schema<Vec<&Vec<sap::Row>>> = Schema {
-                messages: rows.values().collect(),
+            let s: Schema<&Groups<sap::Row>> = Schema {
+                messages: &rows,
                 meta,
             };
 
-            serde_json::to_writer(json_writer, &s).map_err(|err| ImportError::Serialize(err))?;
+            serde_json::to_writer(json_writer, &s).map_err(ImportError::Serialize)?;
         }
         "mye_kam" => {
-            let rows = kam::run(path.clone())?;
+            let rows = kam::run(path.clone(), &sheet_selection(kam::selection(), flags))?;
 
-            let mut meta: HashMap<String, String> = HashMap::new();
+            let mut meta: BTreeMap<String, String> = BTreeMap::new();
             meta.insert("created_at".to_string(), Utc::now().to_string());
 
-            let re = Regex::new(r".*enelteco-kam-(?P<timeframe>[0-9]{4}-[0-9]{2}-[0-9]{2})\.xlsx?")
+            let re = Regex::new(
+                r"(?i).*enelteco-kam-(?P<timeframe>[0-9]{4}-[0-9]{2}-[0-9]{2})\.(xlsx?|ods|csv|tsv|txt)$",
+            )
                 .unwrap();
 
             let timeframe = re.captures(path.as_str()).and_then(|cap| {
@@ -164,48 +324,95 @@ fn run(excel_type: String, path: String) -> Result<(), ImportError> {
                 }
             }
 
-            let s: Schema<Vec<&Vec<kam::Row>>> = Schema {
-                messages: rows.values().collect(),
+            let s: Schema<&Groups<kam::Row>> = Schema {
+                messages: &rows,
                 meta,
             };
 
-            serde_json::to_writer(json_writer, &s).map_err(|err| ImportError::Serialize(err))?;
+            serde_json::to_writer(json_writer, &s).map_err(ImportError::Serialize)?;
         }
         "customer_tag" => {
-            let rows = customer_tag::run(path)?;
+            let rows = customer_tag::run(path, &sheet_selection(customer_tag::selection(), flags))?;
 
-            let mut meta: HashMap<String, String> = HashMap::new();
+            let mut meta: BTreeMap<String, String> = BTreeMap::new();
             meta.insert("created_at".to_string(), Utc::now().to_string());
-            let s: Schema<Vec<&Vec<customer_tag::Row>>> = Schema {
-                messages: rows.values().collect(),
+            let s: Schema<&Groups<customer_tag::Row>> = Schema {
+                messages: &rows,
                 meta,
             };
-            serde_json::to_writer(json_writer, &s).map_err(|err| ImportError::Serialize(err))?;
+            serde_json::to_writer(json_writer, &s).map_err(ImportError::Serialize)?;
         }
         "mye_meterpoint_value" => {
-            let rows = meterpoint_value::run(path)?;
+            let rows = meterpoint_value::run(
+                path,
+                &sheet_selection(meterpoint_value::selection(), flags),
+            )?;
 
-            let mut meta: HashMap<String, String> = HashMap::new();
+            let mut meta: BTreeMap<String, String> = BTreeMap::new();
             meta.insert("created_at".to_string(), Utc::now().to_string());
-            let s = Schema {
-                messages: vec![rows],
-                meta,
-            };
-            serde_json::to_writer(json_writer, &s).map_err(|err| ImportError::Serialize(err))?;
+
+            write_profile(json_writer, rows, meta, flags)?;
         }
         "contact_attempt" => {
-            let rows = contact_attempt::run(path)?;
+            let rows =
+                contact_attempt::run(path, &sheet_selection(contact_attempt::selection(), flags))?;
 
-            let mut meta: HashMap<String, String> = HashMap::new();
+            let mut meta: BTreeMap<String, String> = BTreeMap::new();
             meta.insert("created_at".to_string(), Utc::now().to_string());
-            let s: Schema<Vec<&Vec<contact_attempt::Row>>> = Schema {
-                messages: rows.values().collect(),
+            let s: Schema<&Groups<contact_attempt::Row>> = Schema {
+                messages: &rows,
                 meta,
             };
-            serde_json::to_writer(json_writer, &s).map_err(|err| ImportError::Serialize(err))?;
+            serde_json::to_writer(json_writer, &s).map_err(ImportError::Serialize)?;
         }
         _ => Err(ImportError::UnknownImport(excel_type))?,
     }
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::row::ExcelRow;
+    use crate::{commission, kam, run};
+    use std::path::PathBuf;
+
+    /// A delimited export with the headers of `R` in row `header_row` and no data.
+    fn export<R: ExcelRow>(name: &str, header_row: usize) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("importsctl-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let headers: Vec<&str> = R::HEADERS.iter().map(|h| h.name).collect();
+        let preamble = ";".repeat(headers.len() - 1) + "\n";
+        let content = preamble.repeat(header_row) + &headers.join(";") + "\n";
+
+        let path = dir.join(name);
+        std::fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn timeframe(path: PathBuf) -> serde_json::Value {
+        let json = std::fs::read_to_string(path.with_extension("json")).unwrap();
+        serde_json::from_str::<serde_json::Value>(&json).unwrap()["meta"]["timeframe"].clone()
+    }
+
+    #[test]
+    fn test_timeframe_of_delimited_exports() {
+        let path = export::<commission::Row>("commissions-enelteco-2024-01.csv", 6);
+        run(
+            "mye_commission".to_string(),
+            path.to_string_lossy().to_string(),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(timeframe(path), "2024-01");
+
+        let path = export::<kam::Row>("enelteco-kam-2024-01-31.csv", 0);
+        run(
+            "mye_kam".to_string(),
+            path.to_string_lossy().to_string(),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(timeframe(path), "2024-01-31");
+    }
+}
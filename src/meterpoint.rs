@@ -0,0 +1,111 @@
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+/// Austrian meterpoint id (Zählpunktnummer): `AT`, 6 digit grid operator code, 5 digit postal
+/// code and a 20 character meterpoint number.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+#[serde(transparent)]
+pub struct Meterpoint(String);
+
+impl Meterpoint {
+    /// Removes whitespace and uppercases before validating, so `at 002000 ...` is accepted.
+    pub fn parse(value: &str) -> Result<Meterpoint, String> {
+        let normalised: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_uppercase();
+
+        let chars: Vec<char> = normalised.chars().collect();
+
+        if chars.len() != 33 {
+            return Err(format!(
+                "Invalid meterpoint '{}': expected 33 characters, found {}",
+                value.trim(),
+                chars.len()
+            ));
+        }
+        if !normalised.starts_with("AT") {
+            return Err(format!(
+                "Invalid meterpoint '{}': country code must be AT",
+                value.trim()
+            ));
+        }
+        if !chars[2..13].iter().all(|c| c.is_ascii_digit()) {
+            return Err(format!(
+                "Invalid meterpoint '{}': grid operator and postal code must be digits",
+                value.trim()
+            ));
+        }
+        if !chars[13..].iter().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(format!(
+                "Invalid meterpoint '{}': meterpoint number must be alphanumeric",
+                value.trim()
+            ));
+        }
+
+        Ok(Meterpoint(normalised))
+    }
+
+    pub fn from_excel_value(v: String) -> Result<Option<Meterpoint>, String> {
+        if v.trim().is_empty() {
+            return Ok(None);
+        }
+        Meterpoint::parse(&v).map(Some)
+    }
+
+    /// Code of the grid operator including the country, e.g. `AT002000`.
+    pub fn grid_operator_code(&self) -> &str {
+        &self.0[..8]
+    }
+
+    pub fn grid_operator(&self) -> Option<&'static str> {
+        match self.grid_operator_code() {
+            "AT001000" => Some("Wiener Netze"),
+            "AT002000" => Some("Netz Niederösterreich"),
+            "AT003000" => Some("Netz Oberösterreich"),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Meterpoint {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl PartialEq<&str> for Meterpoint {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint::Meterpoint;
+
+    #[test]
+    fn test_parse_normalises_and_splits() {
+        let mp = Meterpoint::parse(" at002000 00000 00000000000100003400 ").unwrap();
+
+        assert_eq!(mp, "AT0020000000000000000000100003400");
+        assert_eq!(mp.grid_operator_code(), "AT002000");
+        assert_eq!(mp.grid_operator(), Some("Netz Niederösterreich"));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_ids() {
+        for input in [
+            "",
+            "AT002000000000000000000010000340",
+            "DE0020000000000000000000100003400",
+            "ATX020000000000000000000100003400",
+            "AT00200000000000000000001000034ü0",
+        ] {
+            assert!(Meterpoint::parse(input).is_err(), "{}", input);
+        }
+
+        assert_eq!(Meterpoint::from_excel_value(" ".to_string()), Ok(None));
+    }
+}
@@ -0,0 +1,340 @@
+use crate::meterpoint_value::{timezone, Data, Stamp};
+use crate::ImportError;
+use chrono::{Duration, NaiveDateTime};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+
+/// Origin of a value, ordered from most to least trustworthy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Quality {
+    Measured,
+    Interpolated,
+    Substituted,
+    Zero,
+    Missing,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// linear interpolation for short gaps, profile substitution for longer ones
+    Auto,
+    Linear,
+    Profile,
+    Zero,
+}
+
+impl FromStr for Strategy {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "auto" => Ok(Strategy::Auto),
+            "linear" => Ok(Strategy::Linear),
+            "profile" => Ok(Strategy::Profile),
+            "zero" => Ok(Strategy::Zero),
+            _ => Err(ImportError::Error(format!("Unknown fill strategy {}", s))),
+        }
+    }
+}
+
+fn interpolate(series: &mut [Option<f64>], quality: &mut [Quality], start: usize, end: usize) {
+    let (before, after) = match (series[start - 1], series[end]) {
+        (Some(before), Some(after)) => (before, after),
+        _ => return,
+    };
+    let steps = (end - start + 1) as f64;
+    for i in start..end {
+        let fraction = (i - start + 1) as f64 / steps;
+        series[i] = Some(before + (after - before) * fraction);
+        quality[i] = Quality::Interpolated;
+    }
+}
+
+/// Takes the value of the same weekday and time from the nearest week that has a measured value.
+/// In the repeated hour of october the first pass takes the first and the second pass the second
+/// of two intervals with the same time.
+fn substitute(
+    series: &mut [Option<f64>],
+    quality: &mut [Quality],
+    labels: &[NaiveDateTime],
+    positions: &HashMap<NaiveDateTime, Vec<usize>>,
+    start: usize,
+    end: usize,
+) {
+    let weeks = (labels[labels.len() - 1] - labels[0]).num_weeks() + 1;
+    for i in start..end {
+        let pass = positions[&labels[i]]
+            .iter()
+            .position(|j| *j == i)
+            .unwrap_or(0);
+        let value = (1..=weeks)
+            .flat_map(|week| [-week, week])
+            .filter_map(|week| positions.get(&(labels[i] + Duration::weeks(week))))
+            .filter_map(|candidates| candidates.get(pass).or(candidates.last()))
+            .find(|j| quality[**j] == Quality::Measured)
+            .and_then(|j| series[*j]);
+        if let Some(value) = value {
+            series[i] = Some(value);
+            quality[i] = Quality::Substituted;
+        }
+    }
+}
+
+/// Fills missing intervals and empty values. The result has a regular 15 minute index and a
+/// quality matrix that tells measured values from estimated ones.
+pub fn fill(data: &Data, strategy: Strategy, max_linear_gap: usize) -> Data {
+    let instants = data.instants();
+    let mut rows: HashMap<NaiveDateTime, usize> = HashMap::new();
+    for (i, instant) in instants.iter().enumerate() {
+        rows.entry(*instant).or_insert(i);
+    }
+
+    let mut grid: Vec<NaiveDateTime> = vec![];
+    if let (Some(first), Some(last)) = (instants.iter().min(), instants.iter().max()) {
+        let mut instant = *first;
+        while instant <= *last {
+            grid.push(instant);
+            instant += Data::interval();
+        }
+    }
+
+    // local interval starts are used to find the same weekday and time in other weeks, each
+    // time of the repeated hour in october has two positions
+    let labels: Vec<NaiveDateTime> = grid.iter().map(|i| timezone::to_local(*i)).collect();
+    let mut positions: HashMap<NaiveDateTime, Vec<usize>> = HashMap::new();
+    for (i, label) in labels.iter().enumerate() {
+        positions.entry(*label).or_default().push(i);
+    }
+
+    let mut columns: Vec<(Vec<Option<f64>>, Vec<Quality>)> = vec![];
+    for c in 0..data.columns.len() {
+        let mut series: Vec<Option<f64>> = grid
+            .iter()
+            .map(|instant| {
+                rows.get(instant)
+                    .and_then(|i| data.data[*i].get(c).cloned().flatten())
+            })
+            .collect();
+        let mut quality: Vec<Quality> = series
+            .iter()
+            .map(|v| match v {
+                Some(_) => Quality::Measured,
+                None => Quality::Missing,
+            })
+            .collect();
+
+        let mut i = 0;
+        while i < series.len() {
+            if series[i].is_some() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < series.len() && series[i].is_none() {
+                i += 1;
+            }
+            let bounded = start > 0 && i < series.len();
+            let short = bounded && i - start <= max_linear_gap;
+
+            match strategy {
+                Strategy::Zero => {
+                    for j in start..i {
+                        series[j] = Some(0.0);
+                        quality[j] = Quality::Zero;
+                    }
+                }
+                Strategy::Linear if short => interpolate(&mut series, &mut quality, start, i),
+                Strategy::Linear => {}
+                Strategy::Auto if short => interpolate(&mut series, &mut quality, start, i),
+                Strategy::Auto | Strategy::Profile => {
+                    substitute(&mut series, &mut quality, &labels, &positions, start, i)
+                }
+            }
+        }
+        columns.push((series, quality));
+    }
+
+    let index = labels
+        .iter()
+        .map(|label| match data.stamp {
+            Stamp::Start => *label,
+            Stamp::End => *label + Data::interval(),
+        })
+        .collect();
+
+    Data {
+        columns: data.columns.clone(),
+        registers: data.registers.clone(),
+        index,
+        data: (0..grid.len())
+            .map(|i| columns.iter().map(|(series, _)| series[i]).collect())
+            .collect(),
+        stamp: data.stamp,
+        quality: Some(
+            (0..grid.len())
+                .map(|i| columns.iter().map(|(_, quality)| quality[i]).collect())
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::fill::{fill, Quality, Strategy};
+    use crate::meterpoint_value::register::Register;
+    use crate::meterpoint_value::{timezone, Data, Stamp};
+    use chrono::{Duration, NaiveDate, NaiveDateTime, Timelike};
+
+    const AT1: &str = "AT0020000000000000000000000000001";
+
+    fn data(values: Vec<Option<f64>>) -> Data {
+        let start = NaiveDate::from_ymd_opt(2023, 1, 2)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let index: Vec<NaiveDateTime> = (0..values.len())
+            .map(|i| start + Duration::minutes(15 * i as i64))
+            .collect();
+        Data {
+            columns: vec![Meterpoint::parse(AT1).unwrap()],
+            registers: vec![Register::ActiveImport],
+            index,
+            data: values.into_iter().map(|v| vec![v]).collect(),
+            stamp: Stamp::Start,
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn test_fill_interpolates_short_gaps_and_missing_intervals() {
+        let mut d = data(vec![Some(1.0), None, Some(3.0), Some(4.0), Some(8.0)]);
+        // the 4th interval is not in the file at all
+        d.index.remove(3);
+        d.data.remove(3);
+
+        let result = fill(&d, Strategy::Linear, 4);
+        assert_eq!(result.index.len(), 5);
+        assert_eq!(
+            result.data,
+            vec![
+                vec![Some(1.0)],
+                vec![Some(2.0)],
+                vec![Some(3.0)],
+                vec![Some(5.5)],
+                vec![Some(8.0)]
+            ]
+        );
+        assert_eq!(
+            result.quality.unwrap(),
+            vec![
+                vec![Quality::Measured],
+                vec![Quality::Interpolated],
+                vec![Quality::Measured],
+                vec![Quality::Interpolated],
+                vec![Quality::Measured]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fill_substitutes_long_gaps_from_previous_week() {
+        let week = 7 * 96;
+        let mut values: Vec<Option<f64>> = (0..week + 10).map(|i| Some(i as f64)).collect();
+        for v in values.iter_mut().skip(week).take(6) {
+            *v = None;
+        }
+
+        let result = fill(&data(values.clone()), Strategy::Auto, 4);
+        assert_eq!(result.data[week], vec![Some(0.0)]);
+        assert_eq!(result.data[week + 5], vec![Some(5.0)]);
+        assert_eq!(
+            result.quality.as_ref().unwrap()[week],
+            vec![Quality::Substituted]
+        );
+
+        let result = fill(&data(values), Strategy::Zero, 4);
+        assert_eq!(result.data[week + 1], vec![Some(0.0)]);
+        assert_eq!(
+            result.quality.as_ref().unwrap()[week + 1],
+            vec![Quality::Zero]
+        );
+        assert_eq!(
+            result.quality.as_ref().unwrap()[week + 6],
+            vec![Quality::Measured]
+        );
+    }
+
+    /// Two weeks around the end of summer time 2023, `value` gives every interval by its local
+    /// start and whether it is the second pass of the repeated hour.
+    fn autumn(value: impl Fn(NaiveDateTime, bool) -> Option<f64>) -> Data {
+        let first = NaiveDate::from_ymd_opt(2023, 10, 21)
+            .unwrap()
+            .and_hms_opt(22, 0, 0)
+            .unwrap();
+        let mut index = vec![];
+        let mut data = vec![];
+        for i in 0..(15 * 96 + 4) {
+            let utc = first + Duration::minutes(15 * i);
+            let local = timezone::to_local(utc);
+            index.push(local);
+            data.push(vec![value(
+                local,
+                index.iter().filter(|l| **l == local).count() == 2,
+            )]);
+        }
+        Data {
+            columns: vec![Meterpoint::parse(AT1).unwrap()],
+            registers: vec![Register::ActiveImport],
+            index,
+            data,
+            stamp: Stamp::Start,
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn test_fill_substitutes_the_repeated_hour() {
+        let day = |d: u32| NaiveDate::from_ymd_opt(2023, 10, d).unwrap();
+        let repeated = |local: NaiveDateTime| local.date() == day(29) && local.hour() == 2;
+
+        // a gap in the first pass is filled from the week before, the second pass stays
+        let result = fill(
+            &autumn(|local, second| match (repeated(local), second) {
+                (true, false) => None,
+                (true, true) => Some(2.0),
+                _ if local.hour() == 2 && local.date() == day(22) => Some(3.0),
+                _ => Some(5.0),
+            }),
+            Strategy::Profile,
+            4,
+        );
+        let passes: Vec<(Option<f64>, Quality)> = (0..result.index.len())
+            .filter(|i| repeated(result.index[*i]))
+            .map(|i| (result.data[i][0], result.quality.as_ref().unwrap()[i][0]))
+            .collect();
+        assert_eq!(passes.len(), 8);
+        assert!(passes[..4] == [(Some(3.0), Quality::Substituted); 4]);
+        assert!(passes[4..] == [(Some(2.0), Quality::Measured); 4]);
+
+        // a week later the same time takes the first pass
+        let november = NaiveDate::from_ymd_opt(2023, 11, 5).unwrap();
+        let result = fill(
+            &autumn(|local, second| match (repeated(local), second) {
+                (true, false) => Some(1.0),
+                (true, true) => Some(2.0),
+                _ if local.date() == november && local.hour() == 2 => None,
+                _ => Some(5.0),
+            }),
+            Strategy::Profile,
+            4,
+        );
+        let filled: Vec<Option<f64>> = (0..result.index.len())
+            .filter(|i| result.index[*i].date() == november && result.index[*i].hour() == 2)
+            .map(|i| result.data[i][0])
+            .collect();
+        assert_eq!(filled, vec![Some(1.0); 4]);
+    }
+}
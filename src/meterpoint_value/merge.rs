@@ -0,0 +1,206 @@
+use crate::meterpoint::Meterpoint;
+use crate::meterpoint_value::register::Register;
+use crate::meterpoint_value::{timezone, Data, Stamp};
+use crate::ImportError;
+use chrono::NaiveDateTime;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+/// How to resolve a meterpoint that has values for the same interval in more than one file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overlap {
+    /// the value of the later profile, i.e. of the file given later on the command line
+    Newer,
+    Error,
+    Average,
+}
+
+impl FromStr for Overlap {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "newer" => Ok(Overlap::Newer),
+            "error" => Ok(Overlap::Error),
+            "average" => Ok(Overlap::Average),
+            _ => Err(ImportError::Error(format!("Unknown overlap policy {}", s))),
+        }
+    }
+}
+
+/// Merges profiles into one series on a common time index. Profiles are applied in the given
+/// order, from oldest to newest, the index of the result uses the stamp of the first profile.
+pub fn merge(profiles: Vec<Data>, overlap: Overlap) -> Result<Data, ImportError> {
+    let stamp = profiles.first().map_or(Stamp::Start, |p| p.stamp);
+
+    // consumption and feed-in of the same meterpoint stay separate columns
+    let mut columns: Vec<(Meterpoint, Register)> = vec![];
+    let mut values: BTreeMap<NaiveDateTime, Vec<Vec<f64>>> = BTreeMap::new();
+    for profile in profiles.iter() {
+        let targets: Vec<usize> = profile
+            .columns
+            .iter()
+            .cloned()
+            .zip(profile.registers.iter().cloned())
+            .map(|column| match columns.iter().position(|c| *c == column) {
+                Some(c) => c,
+                None => {
+                    columns.push(column);
+                    columns.len() - 1
+                }
+            })
+            .collect();
+
+        for (i, instant) in profile.instants().into_iter().enumerate() {
+            let row = values.entry(instant).or_default();
+            row.resize(columns.len(), vec![]);
+            for (c, target) in targets.iter().enumerate() {
+                if let Some(v) = profile.data[i].get(c).cloned().flatten() {
+                    row[*target].push(v);
+                }
+            }
+        }
+    }
+
+    let (columns, registers) = columns.into_iter().unzip();
+    let mut r = Data {
+        columns,
+        registers,
+        index: vec![],
+        data: vec![],
+        stamp,
+        quality: None,
+    };
+
+    for (instant, row) in values {
+        let label = match stamp {
+            Stamp::Start => timezone::to_local(instant),
+            Stamp::End => timezone::to_local(instant) + Data::interval(),
+        };
+
+        let mut merged: Vec<Option<f64>> = Vec::with_capacity(r.columns.len());
+        for c in 0..r.columns.len() {
+            let candidates = row.get(c).map(|v| v.as_slice()).unwrap_or_default();
+            let value = match (candidates.last(), overlap) {
+                (None, _) => None,
+                (Some(v), Overlap::Newer) => Some(*v),
+                (Some(v), Overlap::Error) => {
+                    if candidates.iter().any(|other| other != v) {
+                        return Err(ImportError::Error(format!(
+                            "Overlapping values for {} at {}",
+                            r.label(c),
+                            label
+                        )));
+                    }
+                    Some(*v)
+                }
+                (Some(_), Overlap::Average) => {
+                    Some(candidates.iter().sum::<f64>() / candidates.len() as f64)
+                }
+            };
+            merged.push(value);
+        }
+
+        r.index.push(label);
+        r.data.push(merged);
+    }
+
+    Ok(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::merge::{merge, Overlap};
+    use crate::meterpoint_value::register::Register;
+    use crate::meterpoint_value::{Data, Stamp};
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    const AT1: &str = "AT0020000000000000000000000000001";
+    const AT2: &str = "AT0020000000000000000000000000002";
+
+    fn profiles() -> (Vec<NaiveDateTime>, Vec<Data>) {
+        let d = NaiveDate::from_ymd_opt(2023, 1, 5).unwrap();
+        let index = vec![
+            d.and_hms_opt(0, 0, 0).unwrap(),
+            d.and_hms_opt(0, 15, 0).unwrap(),
+            d.and_hms_opt(0, 30, 0).unwrap(),
+        ];
+        let older = Data {
+            columns: vec![Meterpoint::parse(AT1).unwrap()],
+            registers: vec![Register::ActiveImport],
+            index: index[..2].to_vec(),
+            data: vec![vec![Some(1.0)], vec![Some(2.0)]],
+            stamp: Stamp::Start,
+            quality: None,
+        };
+        // the same intervals with end stamps
+        let newer = Data {
+            columns: vec![
+                Meterpoint::parse(AT1).unwrap(),
+                Meterpoint::parse(AT2).unwrap(),
+                Meterpoint::parse(AT1).unwrap(),
+            ],
+            registers: vec![
+                Register::ActiveExport,
+                Register::ActiveImport,
+                Register::ActiveImport,
+            ],
+            index: index[1..].to_vec(),
+            data: vec![
+                vec![Some(0.5), Some(5.0), Some(4.0)],
+                vec![Some(0.5), None, Some(6.0)],
+            ],
+            stamp: Stamp::End,
+            quality: None,
+        };
+        (index, vec![older, newer])
+    }
+
+    #[test]
+    fn test_merge_unions_columns_and_index() {
+        let (index, profiles) = profiles();
+        let result = merge(profiles, Overlap::Newer).unwrap();
+
+        assert_eq!(
+            result.columns,
+            vec![
+                Meterpoint::parse(AT1).unwrap(),
+                Meterpoint::parse(AT1).unwrap(),
+                Meterpoint::parse(AT2).unwrap()
+            ]
+        );
+        assert_eq!(
+            result.registers,
+            vec![
+                Register::ActiveImport,
+                Register::ActiveExport,
+                Register::ActiveImport
+            ]
+        );
+        assert_eq!(result.index, index[..2].to_vec());
+        assert_eq!(
+            result.data,
+            vec![
+                vec![Some(4.0), Some(0.5), Some(5.0)],
+                vec![Some(6.0), Some(0.5), None]
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_overlap_policies() {
+        let (_, p) = profiles();
+        let result = merge(p, Overlap::Average).unwrap();
+        assert_eq!(result.data[0], vec![Some(2.5), Some(0.5), Some(5.0)]);
+
+        let (_, p) = profiles();
+        assert!(merge(p, Overlap::Error).is_err());
+
+        // newer is the later profile in the list
+        let (_, mut p) = profiles();
+        p.reverse();
+        let result = merge(p, Overlap::Newer).unwrap();
+        assert_eq!(result.data[0], vec![Some(0.5), Some(5.0), Some(1.0)]);
+    }
+}
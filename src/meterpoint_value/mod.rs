@@ -1,22 +1,85 @@
+use crate::meterpoint::Meterpoint;
+use crate::source;
 use crate::ImportError;
-use calamine::{open_workbook_auto, DataType, Range, Reader};
-use chrono::NaiveDateTime;
+use calamine::{DataType, Range};
+use chrono::{Duration, NaiveDateTime};
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::fmt::Debug;
+use std::io::Read;
 
+mod fill;
+mod merge;
+mod mscons;
 mod myelectric;
 mod netze_noe;
 mod netze_ooe;
+mod register;
+mod resample;
+mod timezone;
+mod validate;
 mod wiener_netze;
 
+pub use fill::fill;
+pub use merge::merge;
+pub use resample::resample;
+pub use validate::validate;
+
+/// Whether the index holds the beginning or the end of each 15 minute interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stamp {
+    Start,
+    End,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct Data {
-    columns: Vec<String>,
+    columns: Vec<Meterpoint>,
+    /// register of each column, consumption and feed-in of one meterpoint are separate columns
+    registers: Vec<register::Register>,
     index: Vec<NaiveDateTime>,
     data: Vec<Vec<Option<f64>>>,
+    #[serde(skip)]
+    stamp: Stamp,
+    /// origin of every value, only present after gaps were filled
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quality: Option<Vec<Vec<fill::Quality>>>,
+}
+
+impl Data {
+    pub fn interval() -> Duration {
+        Duration::minutes(15)
+    }
+
+    /// Meterpoint and register of a column, e.g. `AT0020000000000000000000100003400:2.8.0`.
+    pub fn label(&self, column: usize) -> String {
+        format!("{}:{}", self.columns[column], self.registers[column].obis())
+    }
+
+    /// Adds the grid operator of every meterpoint that belongs to a known operator.
+    pub fn write_meta(&self, meta: &mut BTreeMap<String, String>) {
+        for meterpoint in &self.columns {
+            if let Some(operator) = meterpoint.grid_operator() {
+                meta.insert(
+                    format!("grid_operator.{}", meterpoint),
+                    operator.to_string(),
+                );
+            }
+        }
+    }
+
+    /// Start of every interval in utc.
+    fn instants(&self) -> Vec<NaiveDateTime> {
+        let starts: Vec<NaiveDateTime> = match self.stamp {
+            Stamp::Start => self.index.clone(),
+            Stamp::End => self.index.iter().map(|ts| *ts - Data::interval()).collect(),
+        };
+        timezone::to_utc(&starts)
+    }
 }
 
+#[derive(PartialEq)]
 pub enum Schema {
     MyElectric,
     WienerNetze,
@@ -91,25 +154,77 @@ pub fn detect_schema(sheet: &Range<DataType>) -> Schema {
     if head_check == Some("Werte in kW".to_string()) {
         return Schema::NetzeNoe;
     }
-    return Schema::Unknown;
+    Schema::Unknown
 }
 
-pub fn run(path: String) -> Result<Data, ImportError> {
-    let mut excel = open_workbook_auto(path.clone())?;
-
-    if let Some(Ok(sheet)) = excel.worksheet_range_at(0) {
-        return match detect_schema(&sheet) {
-            Schema::WienerNetze => wiener_netze::run(sheet),
-            Schema::MyElectric => myelectric::run(sheet),
-            Schema::NetzeOoe => netze_ooe::run(sheet, path),
-            Schema::NetzeNoe => netze_noe::run(sheet),
-            Schema::Unknown => Err(ImportError::Error(
-                "Could not detect schema for meterpoint_value import".to_string(),
-            )),
-        };
+/// MSCONS files are recognised by their extension or by the service segments they start with.
+fn is_mscons(path: &str) -> bool {
+    let extension = std::path::Path::new(path)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase());
+    if matches!(extension.as_deref(), Some("edi" | "mscons")) {
+        return true;
     }
 
-    return Err(ImportError::Error(
-        "Could not find any sheet in excel".to_string(),
-    ));
+    let mut head = [0; 3];
+    std::fs::File::open(path)
+        .and_then(|mut file| file.read_exact(&mut head))
+        .is_ok_and(|_| &head == b"UNA" || &head == b"UNB")
+}
+
+/// Load profiles are read from the first sheet unless another one is given on the command line.
+pub fn selection() -> source::Selection {
+    source::Selection::default()
+}
+
+/// Profile of the selected sheets, several sheets of a workbook, e.g. one per year, are merged
+/// and must not overlap. MSCONS files have no sheets to select.
+pub fn run(path: String, selection: &source::Selection) -> Result<Data, ImportError> {
+    if is_mscons(&path) {
+        if *selection != self::selection() {
+            return Err(ImportError::Error(
+                "MSCONS files have no sheets to select".to_string(),
+            ));
+        }
+        let bytes = std::fs::read(&path).map_err(ImportError::IoError)?;
+        // interchanges are usually latin-1 (UNOC)
+        let content = String::from_utf8(bytes)
+            .unwrap_or_else(|e| e.into_bytes().iter().map(|b| *b as char).collect());
+        return mscons::run(&content);
+    }
+
+    let mut profiles = vec![];
+    let mut mismatch = None;
+    for (name, sheet) in source::sheets(&path, selection)? {
+        // reading every sheet skips those without a load profile, e.g. a cover sheet
+        if selection.every_sheet() && detect_schema(&sheet) == Schema::Unknown {
+            mismatch.get_or_insert(ImportError::Sheet(name, Box::new(unknown_schema())));
+            continue;
+        }
+        let profile =
+            from_range(sheet, path.clone()).map_err(|e| ImportError::Sheet(name, Box::new(e)))?;
+        profiles.push(profile);
+    }
+
+    match profiles.len() {
+        0 => Err(mismatch.unwrap_or_else(unknown_schema)),
+        1 => Ok(profiles.remove(0)),
+        _ => merge(profiles, merge::Overlap::Error),
+    }
+}
+
+fn unknown_schema() -> ImportError {
+    ImportError::Error("Could not detect schema for meterpoint_value import".to_string())
+}
+
+/// Parses a sheet of a load profile, `path` is only used for schemas that encode information
+/// in the file name.
+pub fn from_range(sheet: Range<DataType>, path: String) -> Result<Data, ImportError> {
+    match detect_schema(&sheet) {
+        Schema::WienerNetze => wiener_netze::run(sheet),
+        Schema::MyElectric => myelectric::run(sheet),
+        Schema::NetzeOoe => netze_ooe::run(sheet, path),
+        Schema::NetzeNoe => netze_noe::run(sheet),
+        Schema::Unknown => Err(unknown_schema()),
+    }
 }
@@ -0,0 +1,356 @@
+use crate::meterpoint::Meterpoint;
+use crate::meterpoint_value::fill::Quality;
+use crate::meterpoint_value::register::Register;
+use crate::meterpoint_value::{timezone, Data, Stamp};
+use crate::{ImportError, Reason};
+use chrono::{Duration, NaiveDateTime};
+use std::collections::{BTreeMap, BTreeSet};
+
+// MSCONS as sent by austrian grid operators over EDA: every LOC+172 starts a meterpoint, every
+// PIA names the OBIS code of the following values and every QTY is followed by the DTM+163 and
+// DTM+164 segments of its interval.
+
+/// Value and quality by utc interval start.
+type Values = BTreeMap<NaiveDateTime, (f64, Quality)>;
+
+struct Segment {
+    tag: String,
+    elements: Vec<Vec<String>>,
+}
+
+impl Segment {
+    /// Component of a data element, empty if the segment is shorter.
+    fn get(&self, element: usize, component: usize) -> &str {
+        self.elements
+            .get(element)
+            .and_then(|e| e.get(component))
+            .map_or("", |c| c.as_str())
+    }
+}
+
+/// Splits an interchange into segments, honouring the separators of the UNA service string
+/// advice and its release character.
+fn segments(content: &str) -> (Vec<Segment>, char) {
+    let (mut component, mut element, mut decimal, mut release, mut terminator) =
+        (':', '+', '.', '?', '\'');
+    let mut body = content.trim_start_matches('\u{feff}');
+
+    if let Some(rest) = body.strip_prefix("UNA") {
+        let advice: Vec<char> = rest.chars().take(6).collect();
+        if advice.len() == 6 {
+            (component, element, decimal, release, terminator) =
+                (advice[0], advice[1], advice[2], advice[3], advice[5]);
+            body = &rest[advice.iter().map(|c| c.len_utf8()).sum::<usize>()..];
+        }
+    }
+
+    let mut segments = vec![];
+    let mut elements: Vec<Vec<String>> = vec![vec![String::new()]];
+    let mut released = false;
+    for c in body.chars() {
+        if released {
+            released = false;
+        } else if c == release {
+            released = true;
+            continue;
+        } else if c == '\r' || c == '\n' {
+            continue;
+        } else if c == terminator {
+            let tag = elements[0][0].trim().to_string();
+            elements.remove(0);
+            if !tag.is_empty() {
+                segments.push(Segment { tag, elements });
+            }
+            elements = vec![vec![String::new()]];
+            continue;
+        } else if c == element {
+            elements.push(vec![String::new()]);
+            continue;
+        } else if c == component {
+            if let Some(e) = elements.last_mut() {
+                e.push(String::new());
+            }
+            continue;
+        }
+        if let Some(value) = elements.last_mut().and_then(|e| e.last_mut()) {
+            value.push(c);
+        }
+    }
+
+    (segments, decimal)
+}
+
+/// Reads a DTM date as utc. Format 303 carries the utc offset, e.g. `202301010000+01`, format
+/// 203 is austrian wall clock time.
+fn parse_date(segment: &Segment, row: usize) -> Result<NaiveDateTime, ImportError> {
+    let value = segment.get(0, 1);
+    let error = |message: String| {
+        ImportError::ValueError(
+            row,
+            "DTM".to_string(),
+            value.to_string(),
+            Reason::ParseFailure(message),
+        )
+    };
+
+    let local = value
+        .get(..12)
+        .and_then(|ts| NaiveDateTime::parse_from_str(ts, "%Y%m%d%H%M").ok())
+        .ok_or_else(|| error(format!("Could not parse date {}", value)))?;
+
+    match segment.get(0, 2) {
+        "303" => {
+            let hours: i64 = value
+                .get(12..15)
+                .and_then(|h| h.trim_start_matches('+').parse().ok())
+                .ok_or_else(|| error(format!("Could not parse utc offset of {}", value)))?;
+            Ok(local - Duration::hours(hours))
+        }
+        "203" => Ok(timezone::to_utc(&[local])[0]),
+        format => Err(error(format!("Unsupported date format {}", format))),
+    }
+}
+
+/// Energy of one interval in kWh, demand values are converted from kW.
+fn parse_quantity(
+    segment: &Segment,
+    decimal: char,
+    row: usize,
+) -> Result<(f64, Quality), ImportError> {
+    let raw = segment.get(0, 1);
+    let error = |message: String| {
+        ImportError::ValueError(
+            row,
+            "QTY".to_string(),
+            raw.to_string(),
+            Reason::ParseFailure(message),
+        )
+    };
+
+    let value: f64 = raw
+        .replace(decimal, ".")
+        .parse()
+        .map_err(|_| error(format!("Could not parse quantity {}", raw)))?;
+    let value = match segment.get(0, 2).to_uppercase().as_str() {
+        "" | "KWH" | "KVH" | "KVARH" => value,
+        "KWT" | "KW" | "KVR" | "KVAR" => value / 4.0,
+        unit => return Err(error(format!("Unsupported unit {}", unit))),
+    };
+
+    // 220 is a true reading, every other qualifier is an estimate of the grid operator
+    let quality = match segment.get(0, 0) {
+        "220" => Quality::Measured,
+        _ => Quality::Substituted,
+    };
+
+    Ok((value, quality))
+}
+
+pub fn run(content: &str) -> Result<Data, ImportError> {
+    let (segments, decimal) = segments(content);
+
+    let mut series: Vec<((Meterpoint, Register), Values)> = vec![];
+    let mut meterpoint: Option<Meterpoint> = None;
+    let mut register = Register::ActiveImport;
+    let mut quantity: Option<(f64, Quality)> = None;
+    let mut start: Option<NaiveDateTime> = None;
+
+    for (i, segment) in segments.iter().enumerate() {
+        match (segment.tag.as_str(), segment.get(0, 0)) {
+            ("LOC", "172") => {
+                meterpoint = Some(Meterpoint::parse(segment.get(1, 0)).map_err(|e| {
+                    ImportError::ValueError(
+                        i,
+                        "LOC".to_string(),
+                        segment.get(1, 0).to_string(),
+                        Reason::ParseFailure(e),
+                    )
+                })?);
+                register = Register::ActiveImport;
+            }
+            ("PIA", _) => {
+                register = Register::detect(segment.get(1, 0)).ok_or_else(|| {
+                    ImportError::ValueError(
+                        i,
+                        "PIA".to_string(),
+                        segment.get(1, 0).to_string(),
+                        Reason::UnknownEnumValue,
+                    )
+                })?;
+            }
+            ("QTY", _) => {
+                quantity = Some(parse_quantity(segment, decimal, i)?);
+                start = None;
+            }
+            // period of the whole message, only interval dates follow a QTY
+            ("DTM", "163") if quantity.is_some() => start = Some(parse_date(segment, i)?),
+            ("DTM", "164") if quantity.is_some() => {
+                let end = parse_date(segment, i)?;
+                let start = start.ok_or_else(|| {
+                    ImportError::ValueError(
+                        i,
+                        "DTM".to_string(),
+                        segment.get(0, 1).to_string(),
+                        Reason::ParseFailure("Interval end without start".to_string()),
+                    )
+                })?;
+                if end - start != Data::interval() {
+                    return Err(ImportError::ValueError(
+                        i,
+                        "DTM".to_string(),
+                        segment.get(0, 1).to_string(),
+                        Reason::ParseFailure(format!(
+                            "Unsupported interval of {} minutes",
+                            (end - start).num_minutes()
+                        )),
+                    ));
+                }
+
+                let key = (
+                    meterpoint.clone().ok_or_else(|| {
+                        ImportError::ValueError(i, "LOC".to_string(), String::new(), Reason::Empty)
+                    })?,
+                    register,
+                );
+                let position = match series.iter().position(|(k, _)| *k == key) {
+                    Some(position) => position,
+                    None => {
+                        series.push((key, BTreeMap::new()));
+                        series.len() - 1
+                    }
+                };
+                if let Some(value) = quantity.take() {
+                    series[position].1.insert(start, value);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let instants: BTreeSet<NaiveDateTime> = series
+        .iter()
+        .flat_map(|(_, values)| values.keys().cloned())
+        .collect();
+    let value = |instant: &NaiveDateTime, c: usize| series[c].1.get(instant);
+
+    let estimated = series
+        .iter()
+        .flat_map(|(_, values)| values.values())
+        .any(|(_, quality)| *quality != Quality::Measured);
+
+    Ok(Data {
+        columns: series.iter().map(|((mp, _), _)| mp.clone()).collect(),
+        registers: series.iter().map(|((_, register), _)| *register).collect(),
+        index: instants.iter().map(|i| timezone::to_local(*i)).collect(),
+        data: instants
+            .iter()
+            .map(|i| {
+                (0..series.len())
+                    .map(|c| value(i, c).map(|v| v.0))
+                    .collect()
+            })
+            .collect(),
+        stamp: Stamp::Start,
+        quality: estimated.then(|| {
+            instants
+                .iter()
+                .map(|i| {
+                    (0..series.len())
+                        .map(|c| value(i, c).map_or(Quality::Missing, |v| v.1))
+                        .collect()
+                })
+                .collect()
+        }),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::fill::Quality;
+    use crate::meterpoint_value::mscons::segments;
+    use crate::meterpoint_value::register::Register;
+    use crate::meterpoint_value::{run, selection};
+    use crate::source::Selection;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_segments_honour_una_and_release_character() {
+        let (segments, decimal) =
+            segments("UNA:+,? 'DTM+163:202301010000?+01:303'\nPIA+5+1-1?:2.8.0:SRW'");
+
+        assert_eq!(decimal, ',');
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].tag, "DTM");
+        assert_eq!(segments[0].get(0, 1), "202301010000+01");
+        assert_eq!(segments[1].get(1, 0), "1-1:2.8.0");
+        assert_eq!(segments[1].get(5, 5), "");
+    }
+
+    #[test]
+    fn test_parse_mscons_is_successful() {
+        let data = run("var/meterpoint_value_mscons.edi".to_string(), &selection()).unwrap();
+
+        assert_eq!(
+            data.columns,
+            vec![
+                Meterpoint::parse("AT0020000000000000000000100003400").unwrap(),
+                Meterpoint::parse("AT0020000000000000000000100003400").unwrap(),
+            ]
+        );
+        assert_eq!(
+            data.registers,
+            vec![Register::ActiveImport, Register::ActiveExport]
+        );
+
+        let d = NaiveDate::from_ymd_opt(2023, 1, 1).unwrap();
+        assert_eq!(
+            data.index,
+            vec![
+                d.and_hms_opt(0, 0, 0).unwrap(),
+                d.and_hms_opt(0, 15, 0).unwrap(),
+                d.and_hms_opt(0, 30, 0).unwrap(),
+                d.and_hms_opt(0, 45, 0).unwrap(),
+            ]
+        );
+        assert_eq!(
+            data.data,
+            vec![
+                vec![Some(0.125), Some(0.0)],
+                vec![Some(0.25), Some(0.0)],
+                vec![Some(0.5), Some(0.75)],
+                vec![Some(1.0), None],
+            ]
+        );
+        assert_eq!(
+            data.quality.unwrap()[2..],
+            vec![
+                vec![Quality::Measured, Quality::Substituted],
+                vec![Quality::Measured, Quality::Missing],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_mscons_rejects_sheet_selection() {
+        let all_sheets = Selection {
+            all: true,
+            ..selection()
+        };
+        assert!(run("var/meterpoint_value_mscons.edi".to_string(), &all_sheets).is_err());
+    }
+
+    #[test]
+    fn test_parse_mscons_converts_summer_time() {
+        let data = super::run(
+            "UNA:+.? 'LOC+172+AT0020000000000000000000100003400'QTY+220:4:KW'\
+             DTM+163:202307010000?+02:303'DTM+164:202307010015?+02:303'",
+        )
+        .unwrap();
+
+        let d = NaiveDate::from_ymd_opt(2023, 7, 1).unwrap();
+        assert_eq!(data.index, vec![d.and_hms_opt(0, 0, 0).unwrap()]);
+        assert_eq!(data.data, vec![vec![Some(1.0)]]);
+        assert!(data.quality.is_none());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::meterpoint_value::Data;
+use crate::excel;
+use crate::meterpoint_value::register::parse_headers;
+use crate::meterpoint_value::{Data, Stamp};
 use crate::ImportError;
 use calamine::{DataType, Range};
 use chrono::SubsecRound;
@@ -7,52 +9,42 @@ pub fn run(sheet: Range<DataType>) -> Result<Data, ImportError> {
     let header_row = 0;
     let data_start_row = 1;
 
-    let headers: Vec<String> = sheet
-        .rows()
-        .nth(header_row)
-        .unwrap()
-        .iter()
-        .skip(1)
-        .map(|header_cell| {
-            return match header_cell.get_string() {
-                None => String::from(""),
-                Some(v) => v[..33].to_string(),
-            };
-        })
-        .collect();
+    let (headers, registers) = parse_headers(
+        excel::cells_from(excel::row(&sheet, header_row)?, 1),
+        header_row,
+    )?;
 
     let header_cnt = headers.len();
     let mut r = Data {
         columns: headers,
+        registers,
         index: vec![],
         data: vec![],
+        stamp: Stamp::Start,
+        quality: None,
     };
 
     for (i, row) in sheet.rows().enumerate().skip(data_start_row) {
-        let summary_row = row[0].to_string();
+        let summary_row = excel::cell(row, 0).to_string();
         if summary_row == "Summe" || summary_row == "Sum" {
             println!("found");
             // meterpoint_value files contain a summary row
             break;
         }
-        let date = row[0]
-            .as_datetime()
-            .ok_or_else(|| {
-                ImportError::ValueError(
-                    i,
-                    "Timestamp".to_string(),
-                    "could not parse datetime".to_string(),
-                )
-            })?
-            .round_subsecs(0);
+        let date = excel::required(
+            i,
+            "Timestamp",
+            excel::cell(row, 0),
+            "datetime",
+            excel::as_datetime,
+        )?
+        .round_subsecs(0);
 
         let values = row
             .iter()
             .skip(1)
             .take(header_cnt)
-            .map(|v| {
-                return v.get_float();
-            })
+            .map(excel::as_float)
             .collect();
 
         r.index.push(date);
@@ -64,12 +56,13 @@ pub fn run(sheet: Range<DataType>) -> Result<Data, ImportError> {
 
 #[cfg(test)]
 mod tests {
-    use crate::meterpoint_value::run;
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::{run, selection};
     use chrono::NaiveDate;
 
     #[test]
     fn test_parse_myelectric_is_successful() {
-        let result = run("var/meterpoint_value.xlsx".to_string());
+        let result = run("var/meterpoint_value.xlsx".to_string(), &selection());
         assert!(result.is_ok());
 
         let data = result.unwrap();
@@ -77,8 +70,8 @@ mod tests {
         assert_eq!(
             data.columns,
             vec![
-                String::from("AT0020000000000000000000100003400"),
-                String::from("AT0031000000000000000000141934000")
+                Meterpoint::parse("AT0020000000000000000000100003400").unwrap(),
+                Meterpoint::parse("AT0031000000000000000000141934000").unwrap()
             ]
         );
 
@@ -1,47 +1,41 @@
-use crate::meterpoint_value::Data;
+use crate::excel;
+use crate::meterpoint_value::register::parse_headers;
+use crate::meterpoint_value::{Data, Stamp};
 use crate::ImportError;
 use calamine::{DataType, Range};
 use chrono::{NaiveDateTime, SubsecRound};
 
 pub fn run(sheet: Range<DataType>) -> Result<Data, ImportError> {
-    let headers: Vec<String> = sheet
-        .rows()
-        .nth(0)
-        .unwrap()
-        .iter()
-        .skip(1)
-        .map(|header_cell| {
-            return match header_cell.get_string() {
-                None => String::from(""),
-                Some(v) => v[..33].to_string(),
-            };
-        })
-        .collect();
+    let (headers, registers) = parse_headers(excel::cells_from(excel::row(&sheet, 0)?, 1), 0)?;
 
     let header_cnt = headers.len();
     let mut r = Data {
         columns: headers,
+        registers,
         index: vec![],
         data: Vec::with_capacity(header_cnt),
+        stamp: Stamp::End,
+        quality: None,
     };
 
     // there is an edge case where this does not work but with real data it should never appear
     // the schema has at lease 13 rows so if one file has less than ~11 rows of values it tries
     // to parse empty cells to date which then fails
     for (i, row) in sheet.rows().enumerate().skip(1) {
-        let mut date = row[0].as_datetime();
+        let timestamp = excel::cell(row, 0);
+        let mut date = excel::as_datetime(timestamp);
 
-        if None == date {
-            date =
-                NaiveDateTime::parse_from_str(row[0].to_string().as_str(), "%d.%m.%Y %H:%M").ok();
+        if date.is_none() {
+            date = NaiveDateTime::parse_from_str(timestamp.to_string().as_str(), "%d.%m.%Y %H:%M")
+                .ok();
         }
         let date = date
             .ok_or_else(|| {
-                ImportError::ValueError(
+                excel::value_error(
                     i,
-                    "Timestamp".to_string(),
-                    // "could not parse datetime".to_string(),
-                    format!("Could not parse datetime {}", row[0].to_string()),
+                    "Timestamp",
+                    timestamp,
+                    excel::reason(timestamp, "datetime"),
                 )
             })?
             .round_subsecs(0);
@@ -50,9 +44,7 @@ pub fn run(sheet: Range<DataType>) -> Result<Data, ImportError> {
             .iter()
             .skip(1)
             .take(header_cnt)
-            .map(|v| {
-                return v.get_float();
-            })
+            .map(excel::as_float)
             .collect();
 
         r.index.push(date);
@@ -64,12 +56,16 @@ pub fn run(sheet: Range<DataType>) -> Result<Data, ImportError> {
 
 #[cfg(test)]
 mod tests {
-    use crate::meterpoint_value::run;
-    use chrono::{NaiveDate, NaiveDateTime};
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::{run, selection};
+    use chrono::NaiveDate;
 
     #[test]
     fn test_parse_is_successful() {
-        let result = run("var/meterpoint_value_netze_noe.xlsx".to_string());
+        let result = run(
+            "var/meterpoint_value_netze_noe.xlsx".to_string(),
+            &selection(),
+        );
 
         if !result.is_ok() {
             println!("{:?}", result);
@@ -81,8 +77,8 @@ mod tests {
         assert_eq!(
             data.columns,
             vec![
-                String::from("AT0020000000000000000000100151234"),
-                String::from("AT0020000000000000000000100251234"),
+                Meterpoint::parse("AT0020000000000000000000100151234").unwrap(),
+                Meterpoint::parse("AT0020000000000000000000100251234").unwrap(),
             ]
         );
 
@@ -115,4 +111,39 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_parse_csv_is_successful() {
+        let data = run(
+            "var/meterpoint_value_netze_noe.csv".to_string(),
+            &selection(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            data.columns,
+            vec![
+                Meterpoint::parse("AT0020000000000000000000100151234").unwrap(),
+                Meterpoint::parse("AT0020000000000000000000100251234").unwrap(),
+            ]
+        );
+
+        let d = NaiveDate::from_ymd_opt(2021, 1, 1).unwrap();
+        assert_eq!(
+            data.index,
+            vec![
+                d.and_hms_opt(0, 15, 0).unwrap(),
+                d.and_hms_opt(0, 30, 0).unwrap(),
+                d.and_hms_opt(0, 45, 0).unwrap(),
+            ]
+        );
+        assert_eq!(
+            data.data,
+            vec![
+                vec![Some(6.870), Some(3.180)],
+                vec![Some(6.270), Some(3.240)],
+                vec![Some(5.670), None],
+            ]
+        );
+    }
 }
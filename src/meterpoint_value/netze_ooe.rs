@@ -1,33 +1,34 @@
-use crate::meterpoint_value::Data;
+use crate::excel;
+use crate::meterpoint::Meterpoint;
+use crate::meterpoint_value::register::Register;
+use crate::meterpoint_value::{Data, Stamp};
 use crate::ImportError;
 use calamine::{DataType, Range};
 use chrono::SubsecRound;
 use regex::Regex;
 
+/// Meterpoint of a profile from the name of its file, like `Lastprofil-AT003…1234-2020.xlsx`.
 fn meterpoint_label(path: String) -> Result<String, String> {
     let p = std::path::Path::new(path.as_str());
-    let filename: String = p
-        .file_name()
+    let stem: String = p
+        .file_stem()
         .ok_or_else(|| "path has no filename".to_string())?
         .to_str()
         .ok_or_else(|| "could not convert path to str".to_string())?
         .trim()
         .to_uppercase();
-    println!("{:?}", filename);
 
-    let re = Regex::new(r"^LASTPROFIL[-\s]?([A-Z]{2}[A-Z0-9]{31}).*\.XLSX$").unwrap();
+    let re = Regex::new(r"^LASTPROFIL[-\s]?([A-Z]{2}[A-Z0-9]{31})").unwrap();
 
-    if let Some(captures) = re.captures(filename.as_str()) {
+    if let Some(captures) = re.captures(stem.as_str()) {
         if let Some(meterpoint) = captures.get(1) {
             return Ok(meterpoint.as_str().to_string());
         }
     }
 
-    let meterpoint: String = filename
+    let meterpoint: String = stem
         .strip_prefix("LASTPROFIL")
         .ok_or_else(|| "filename has no prefix lastprofil".to_string())?
-        .strip_suffix(".XLSX")
-        .ok_or_else(|| "filename has no suffix xlsx".to_string())?
         .trim()
         .chars()
         .filter(|c| c.is_ascii_alphanumeric())
@@ -36,43 +37,38 @@ fn meterpoint_label(path: String) -> Result<String, String> {
         return Err(format!("could not get meterpoint from filename '{path}'",));
     }
 
-    return Ok(meterpoint);
+    Ok(meterpoint)
 }
 
 pub fn run(sheet: Range<DataType>, path: String) -> Result<Data, ImportError> {
-    let meterpoint = meterpoint_label(path).map_err(|e| ImportError::Error(e))?;
+    // feed-in profiles are only distinguishable by their filename
+    let register = std::path::Path::new(path.as_str())
+        .file_name()
+        .and_then(|name| Register::detect(&name.to_string_lossy()))
+        .unwrap_or(Register::ActiveImport);
+    let meterpoint = meterpoint_label(path).map_err(ImportError::Error)?;
 
     println!("{:?}", meterpoint);
-    let headers: Vec<String> = vec![meterpoint];
+    let headers: Vec<Meterpoint> =
+        vec![Meterpoint::parse(&meterpoint).map_err(ImportError::Error)?];
 
     let mut r = Data {
         columns: headers,
+        registers: vec![register],
         index: vec![],
         data: vec![],
+        stamp: Stamp::Start,
+        quality: None,
     };
 
     // there is an edge case where this does not work but with real data it should never appear
     // the schema has at lease 13 rows so if one file has less than ~11 rows of values it tries
     // to parse empty cells to date which then fails
     for (i, row) in sheet.rows().enumerate().skip(2) {
-        let date = row[3].as_date().ok_or_else(|| {
-            ImportError::ValueError(
-                i,
-                "Timestamp".to_string(),
-                "could not parse date".to_string(),
-            )
-        })?;
-        let time = row[4]
-            .as_time()
-            .ok_or_else(|| {
-                ImportError::ValueError(
-                    i,
-                    "Timestamp".to_string(),
-                    "could not parse time".to_string(),
-                )
-            })?
+        let date = excel::required(i, "Timestamp", excel::cell(row, 3), "date", excel::as_date)?;
+        let time = excel::required(i, "Timestamp", excel::cell(row, 4), "time", excel::as_time)?
             .round_subsecs(0);
-        let value = row[5].get_float();
+        let value = excel::as_float(excel::cell(row, 5));
         r.index.push(date.and_time(time));
         r.data.push(vec![value]);
     }
@@ -82,13 +78,17 @@ pub fn run(sheet: Range<DataType>, path: String) -> Result<Data, ImportError> {
 
 #[cfg(test)]
 mod tests {
+    use crate::meterpoint::Meterpoint;
     use crate::meterpoint_value::netze_ooe::meterpoint_label;
-    use crate::meterpoint_value::run;
+    use crate::meterpoint_value::{run, selection};
     use chrono::NaiveDate;
 
     #[test]
     fn test_parse_is_successful() {
-        let result = run("var/Lastprofil AT0030000000000000000000000001234.xlsx".to_string());
+        let result = run(
+            "var/Lastprofil AT0030000000000000000000000001234.xlsx".to_string(),
+            &selection(),
+        );
 
         println!("{:?}", result);
         assert!(result.is_ok());
@@ -97,7 +97,7 @@ mod tests {
 
         assert_eq!(
             data.columns,
-            vec![String::from("AT0030000000000000000000000001234"),]
+            vec![Meterpoint::parse("AT0030000000000000000000000001234").unwrap(),]
         );
 
         let d = NaiveDate::from_ymd_opt(2023, 1, 5).unwrap();
@@ -135,6 +135,26 @@ mod tests {
             ]
         )
     }
+    #[test]
+    fn test_parse_csv_is_successful() {
+        let data = run(
+            "var/LASTPROFIL-AT0030000000000000000000000001234.csv".to_string(),
+            &selection(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            data.columns,
+            vec![Meterpoint::parse("AT0030000000000000000000000001234").unwrap()]
+        );
+        let d = NaiveDate::from_ymd_opt(2023, 1, 5).unwrap();
+        assert_eq!(data.index.len(), 11);
+        assert_eq!(data.index[0], d.and_hms_opt(0, 0, 0).unwrap());
+        assert_eq!(data.index[10], d.and_hms_opt(2, 30, 0).unwrap());
+        assert_eq!(data.data[0], vec![Some(1.623)]);
+        assert_eq!(data.data[10], vec![Some(4.743)]);
+    }
+
     #[test]
     fn test_meterpoint_extraction() {
         for (input, expected) in vec![
@@ -156,7 +176,11 @@ mod tests {
             ),
             (
                 "test/lastprofil AT1234567891234567891234567891234.csv".to_string(),
-                Err("filename has no suffix xlsx".to_string()),
+                Ok("AT1234567891234567891234567891234".to_string()),
+            ),
+            (
+                "test/LASTPROFIL-AT1234567891234567891234567891234.TXT".to_string(),
+                Ok("AT1234567891234567891234567891234".to_string()),
             ),
             (
                 "test/lastprofil AT123456789123456789123456789123.xlsx".to_string(),
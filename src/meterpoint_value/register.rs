@@ -0,0 +1,203 @@
+use crate::excel;
+use crate::meterpoint::Meterpoint;
+use crate::{ImportError, Reason};
+use calamine::DataType;
+use regex::Regex;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+/// An OBIS code with its kind (1 to 4) and quantity, `8` is the energy register and `9` or `29`
+/// are demand or interval values.
+fn obis() -> &'static Regex {
+    static OBIS: OnceLock<Regex> = OnceLock::new();
+    OBIS.get_or_init(|| Regex::new(r"(?:^|[^0-9.])((?:1-\d+:)?([1-4])\.(\d{1,2})\.\d)").unwrap())
+}
+
+/// Metering register of a column, serialised as its OBIS code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum Register {
+    #[serde(rename = "1.8.0")]
+    ActiveImport,
+    #[serde(rename = "2.8.0")]
+    ActiveExport,
+    #[serde(rename = "3.8.0")]
+    ReactiveImport,
+    #[serde(rename = "4.8.0")]
+    ReactiveExport,
+}
+
+impl Register {
+    pub fn obis(&self) -> &'static str {
+        match self {
+            Register::ActiveImport => "1.8.0",
+            Register::ActiveExport => "2.8.0",
+            Register::ReactiveImport => "3.8.0",
+            Register::ReactiveExport => "4.8.0",
+        }
+    }
+
+    /// Recognises OBIS codes (`1-1:2.8.0`, `2.8.0`) and german register names. Other OBIS
+    /// quantities like the maximum demand `1.9.0` aren't energy registers and give `None`.
+    pub fn detect(text: &str) -> Option<Register> {
+        if let Some(captures) = obis().captures(text) {
+            if &captures[3] != "8" {
+                return None;
+            }
+            return match &captures[2] {
+                "1" => Some(Register::ActiveImport),
+                "2" => Some(Register::ActiveExport),
+                "3" => Some(Register::ReactiveImport),
+                _ => Some(Register::ReactiveExport),
+            };
+        }
+
+        let text = text.to_lowercase();
+        let reactive = text.contains("blind") || text.contains("kvar");
+        let export = [
+            "einspeisung",
+            "lieferung",
+            "erzeugung",
+            "abgabe",
+            "kapazitiv",
+        ]
+        .iter()
+        .any(|word| text.contains(word));
+        let import = ["verbrauch", "bezug", "induktiv"]
+            .iter()
+            .any(|word| text.contains(word));
+
+        match (reactive, export, import) {
+            (true, true, _) => Some(Register::ReactiveExport),
+            (true, false, _) => Some(Register::ReactiveImport),
+            (false, true, _) => Some(Register::ActiveExport),
+            (false, false, true) => Some(Register::ActiveImport),
+            _ => None,
+        }
+    }
+}
+
+/// Splits a column header like `AT0020000000000000000000100003400 Einspeisung 2.8.0` into the
+/// meterpoint and its register. Headers without register information are consumption.
+pub fn parse_header(header: &str) -> Result<(Meterpoint, Register), String> {
+    static METERPOINT: OnceLock<Regex> = OnceLock::new();
+    let meterpoint = METERPOINT.get_or_init(|| Regex::new(r"(?i)\bAT[0-9A-Z]{31}\b").unwrap());
+
+    match meterpoint.find(header) {
+        Some(m) => {
+            let rest = format!("{} {}", &header[..m.start()], &header[m.end()..]);
+            let register = match (Register::detect(&rest), obis().captures(&rest)) {
+                (Some(register), _) => register,
+                (None, Some(code)) => {
+                    return Err(format!("{} is no energy register", &code[1]));
+                }
+                (None, None) => Register::ActiveImport,
+            };
+            Ok((Meterpoint::parse(m.as_str())?, register))
+        }
+        None => Ok((Meterpoint::parse(header)?, Register::ActiveImport)),
+    }
+}
+
+/// Parses the meterpoint columns of a header row. Empty cells after the last meterpoint are
+/// ignored, they only widen the sheet.
+pub fn parse_headers(
+    cells: &[DataType],
+    row: usize,
+) -> Result<(Vec<Meterpoint>, Vec<Register>), ImportError> {
+    let len = cells
+        .iter()
+        .rposition(|cell| !cell.to_string().trim().is_empty())
+        .map_or(0, |i| i + 1);
+
+    let mut columns = Vec::with_capacity(len);
+    let mut registers = Vec::with_capacity(len);
+    for cell in cells.iter().take(len) {
+        let (meterpoint, register) = parse_header(&cell.to_string())
+            .map_err(|e| excel::value_error(row, "Zählpunkt", cell, Reason::ParseFailure(e)))?;
+        columns.push(meterpoint);
+        registers.push(register);
+    }
+
+    Ok((columns, registers))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint_value::register::{parse_header, parse_headers, Register};
+    use calamine::DataType;
+
+    #[test]
+    fn test_parse_header() {
+        for (input, expected) in [
+            (
+                "AT0020000000000000000000100003400",
+                ("AT0020000000000000000000100003400", Register::ActiveImport),
+            ),
+            (
+                "AT0020000000000000000000100003400 [kWh]",
+                ("AT0020000000000000000000100003400", Register::ActiveImport),
+            ),
+            (
+                "AT0020000000000000000000100003400 Einspeisung",
+                ("AT0020000000000000000000100003400", Register::ActiveExport),
+            ),
+            (
+                "at0020000000000000000000100003400 1-1:2.8.0",
+                ("AT0020000000000000000000100003400", Register::ActiveExport),
+            ),
+            (
+                "Blindenergie Bezug AT0020000000000000000000100003400",
+                (
+                    "AT0020000000000000000000100003400",
+                    Register::ReactiveImport,
+                ),
+            ),
+            (
+                "AT0020000000000000000000100003400 4.8.0",
+                (
+                    "AT0020000000000000000000100003400",
+                    Register::ReactiveExport,
+                ),
+            ),
+            (
+                "AT 002000 00000 00000000000100003400",
+                ("AT0020000000000000000000100003400", Register::ActiveImport),
+            ),
+        ] {
+            let (meterpoint, register) = parse_header(input).unwrap();
+            assert_eq!(meterpoint, expected.0);
+            assert_eq!(register, expected.1);
+        }
+
+        assert!(parse_header("Zählpunkt").is_err());
+    }
+
+    #[test]
+    fn test_demand_codes_are_no_registers() {
+        assert_eq!(Register::detect("1-1:1.8.0"), Some(Register::ActiveImport));
+        assert_eq!(Register::detect("1-1:1.9.0"), None);
+        assert_eq!(Register::detect("Einspeisung 2.29.0"), None);
+        assert_eq!(
+            parse_header("AT0020000000000000000000100003400 1-1:1.29.0"),
+            Err("1-1:1.29.0 is no energy register".to_string())
+        );
+    }
+
+    #[test]
+    fn test_parse_headers_ignores_trailing_empty_cells() {
+        let cells = vec![
+            DataType::String("AT0020000000000000000000100003400".to_string()),
+            DataType::String("AT0020000000000000000000100003400 Einspeisung".to_string()),
+            DataType::Empty,
+        ];
+        let (columns, registers) = parse_headers(&cells, 0).unwrap();
+        assert_eq!(columns.len(), 2);
+        assert_eq!(
+            registers,
+            vec![Register::ActiveImport, Register::ActiveExport]
+        );
+
+        let cells = vec![DataType::Empty, DataType::String("AT00200".to_string())];
+        assert!(parse_headers(&cells, 0).is_err());
+    }
+}
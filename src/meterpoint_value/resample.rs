@@ -0,0 +1,232 @@
+use crate::meterpoint_value::fill::Quality;
+use crate::meterpoint_value::{timezone, Data, Stamp};
+use crate::ImportError;
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Timelike};
+use std::collections::BTreeMap;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Resolution {
+    Hour,
+    Day,
+    Month,
+}
+
+impl FromStr for Resolution {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "hour" => Ok(Resolution::Hour),
+            "day" => Ok(Resolution::Day),
+            "month" => Ok(Resolution::Month),
+            _ => Err(ImportError::Error(format!("Unknown resolution {}", s))),
+        }
+    }
+}
+
+/// Energy values are summed, power values are averaged or reduced to their peak.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Aggregation {
+    Sum,
+    Mean,
+    Max,
+}
+
+impl FromStr for Aggregation {
+    type Err = ImportError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim() {
+            "sum" => Ok(Aggregation::Sum),
+            "mean" => Ok(Aggregation::Mean),
+            "max" => Ok(Aggregation::Max),
+            _ => Err(ImportError::Error(format!("Unknown aggregation {}", s))),
+        }
+    }
+}
+
+fn local_midnight(date: NaiveDate) -> NaiveDateTime {
+    timezone::to_utc(&[date.and_hms_opt(0, 0, 0).unwrap()])[0]
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).unwrap()
+}
+
+fn first_of_next_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = if date.month() == 12 {
+        (date.year() + 1, 1)
+    } else {
+        (date.year(), date.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1).unwrap()
+}
+
+/// Returns start and end of the period containing the utc instant, both in utc.
+fn period(utc: NaiveDateTime, resolution: Resolution) -> (NaiveDateTime, NaiveDateTime) {
+    let date = timezone::to_local(utc).date();
+    match resolution {
+        Resolution::Hour => {
+            let start = utc.with_minute(0).unwrap().with_second(0).unwrap();
+            (start, start + Duration::hours(1))
+        }
+        Resolution::Day => (
+            local_midnight(date),
+            local_midnight(date.succ_opt().unwrap()),
+        ),
+        Resolution::Month => (
+            local_midnight(first_of_month(date)),
+            local_midnight(first_of_next_month(date)),
+        ),
+    }
+}
+
+fn aggregate(values: &[f64], aggregation: Aggregation) -> f64 {
+    match aggregation {
+        Aggregation::Sum => values.iter().sum(),
+        Aggregation::Mean => values.iter().sum::<f64>() / values.len() as f64,
+        Aggregation::Max => values.iter().cloned().fold(f64::NEG_INFINITY, f64::max),
+    }
+}
+
+/// Resamples the 15 minute values to the given resolution. Periods are local calendar periods,
+/// so days with a clock change have 92 or 100 intervals. A period that is not fully covered by
+/// values yields no value instead of a misleading partial sum.
+pub fn resample(data: &Data, resolution: Resolution, aggregation: Aggregation) -> Data {
+    let mut periods: BTreeMap<NaiveDateTime, (NaiveDateTime, Vec<usize>)> = BTreeMap::new();
+    for (i, instant) in data.instants().into_iter().enumerate() {
+        let (start, end) = period(instant, resolution);
+        periods.entry(start).or_insert((end, vec![])).1.push(i);
+    }
+
+    let mut r = Data {
+        columns: data.columns.clone(),
+        registers: data.registers.clone(),
+        index: vec![],
+        data: vec![],
+        stamp: Stamp::Start,
+        quality: data.quality.as_ref().map(|_| vec![]),
+    };
+
+    for (start, (end, rows)) in periods {
+        let expected = ((end - start).num_seconds() / Data::interval().num_seconds()) as usize;
+        let values: Vec<Option<f64>> = (0..data.columns.len())
+            .map(|c| {
+                let values: Vec<f64> = rows
+                    .iter()
+                    .filter_map(|i| data.data[*i].get(c).cloned().flatten())
+                    .collect();
+                if values.len() == expected {
+                    Some(aggregate(&values, aggregation))
+                } else {
+                    None
+                }
+            })
+            .collect();
+
+        // a period is only as good as its worst interval
+        if let (Some(quality), Some(source)) = (r.quality.as_mut(), data.quality.as_ref()) {
+            quality.push(
+                values
+                    .iter()
+                    .enumerate()
+                    .map(|(c, value): (usize, &Option<f64>)| match value {
+                        Some(_) => rows
+                            .iter()
+                            .filter_map(|i| source[*i].get(c).cloned())
+                            .max()
+                            .unwrap_or(Quality::Missing),
+                        None => Quality::Missing,
+                    })
+                    .collect(),
+            );
+        }
+
+        r.index.push(timezone::to_local(start));
+        r.data.push(values);
+    }
+
+    r
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::register::Register;
+    use crate::meterpoint_value::resample::{resample, Aggregation, Resolution};
+    use crate::meterpoint_value::{Data, Stamp};
+    use chrono::{Duration, NaiveDate, NaiveDateTime};
+
+    const AT1: &str = "AT0020000000000000000000000000001";
+
+    fn data(start: NaiveDateTime, values: Vec<Option<f64>>, stamp: Stamp) -> Data {
+        Data {
+            columns: vec![Meterpoint::parse(AT1).unwrap()],
+            registers: vec![Register::ActiveImport],
+            index: (0..values.len())
+                .map(|i| start + Duration::minutes(15 * i as i64))
+                .collect(),
+            data: values.into_iter().map(|v| vec![v]).collect(),
+            stamp,
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn test_resample_hour_skips_partial_periods() {
+        let d = NaiveDate::from_ymd_opt(2023, 1, 5).unwrap();
+        let values = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0]
+            .into_iter()
+            .map(Some)
+            .collect();
+        let result = resample(
+            &data(d.and_hms_opt(0, 15, 0).unwrap(), values, Stamp::End),
+            Resolution::Hour,
+            Aggregation::Sum,
+        );
+
+        assert_eq!(
+            result.index,
+            vec![
+                d.and_hms_opt(0, 0, 0).unwrap(),
+                d.and_hms_opt(1, 0, 0).unwrap()
+            ]
+        );
+        assert_eq!(result.data, vec![vec![Some(10.0)], vec![None]]);
+    }
+
+    #[test]
+    fn test_resample_day_with_clock_change() {
+        let d = NaiveDate::from_ymd_opt(2023, 3, 26).unwrap();
+        let start = d.and_hms_opt(0, 0, 0).unwrap();
+        // 92 intervals in local time, listed without the skipped hour
+        let index: Vec<NaiveDateTime> = (0..96)
+            .map(|i| start + Duration::minutes(15 * i))
+            .filter(|ts| {
+                *ts < d.and_hms_opt(2, 0, 0).unwrap() || *ts >= d.and_hms_opt(3, 0, 0).unwrap()
+            })
+            .collect();
+        let mut data = data(start, vec![Some(0.5); 92], Stamp::Start);
+        data.index = index;
+
+        let sum = resample(&data, Resolution::Day, Aggregation::Sum);
+        assert_eq!(sum.index, vec![start]);
+        assert_eq!(sum.data, vec![vec![Some(46.0)]]);
+
+        data.data[10] = vec![Some(4.0)];
+        let max = resample(&data, Resolution::Month, Aggregation::Max);
+        assert_eq!(
+            max.index,
+            vec![NaiveDate::from_ymd_opt(2023, 3, 1)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()]
+        );
+        assert_eq!(max.data, vec![vec![None]]);
+
+        let mean = resample(&data, Resolution::Hour, Aggregation::Mean);
+        assert_eq!(mean.index.len(), 23);
+        assert_eq!(mean.data[2], vec![Some(1.375)]);
+    }
+}
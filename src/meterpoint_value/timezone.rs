@@ -0,0 +1,111 @@
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, Weekday};
+
+// load profiles are exported in austrian wall clock time (CET/CEST), so one hour is missing in
+// march and one hour is repeated in october. everything that compares timestamps goes through
+// utc to get a regular grid.
+
+fn last_sunday(year: i32, month: u32) -> NaiveDate {
+    let (next_year, next_month) = if month == 12 {
+        (year + 1, 1)
+    } else {
+        (year, month + 1)
+    };
+    let mut day = NaiveDate::from_ymd_opt(next_year, next_month, 1)
+        .and_then(|d| d.pred_opt())
+        .expect("month has a last day");
+    while day.weekday() != Weekday::Sun {
+        day = day.pred_opt().expect("month has a sunday");
+    }
+    day
+}
+
+fn is_dst(utc: NaiveDateTime) -> bool {
+    let year = utc.year();
+    let start = last_sunday(year, 3).and_hms_opt(1, 0, 0).unwrap();
+    let end = last_sunday(year, 10).and_hms_opt(1, 0, 0).unwrap();
+    utc >= start && utc < end
+}
+
+pub fn utc_offset(utc: NaiveDateTime) -> Duration {
+    if is_dst(utc) {
+        Duration::hours(2)
+    } else {
+        Duration::hours(1)
+    }
+}
+
+pub fn to_local(utc: NaiveDateTime) -> NaiveDateTime {
+    utc + utc_offset(utc)
+}
+
+/// Converts a series of wall clock timestamps to utc. Ambiguous timestamps from the repeated
+/// hour in october are resolved by their position in the series: the first occurrence is read
+/// as summer time, anything that would go backwards as winter time.
+pub fn to_utc(local: &[NaiveDateTime]) -> Vec<NaiveDateTime> {
+    let mut result: Vec<NaiveDateTime> = Vec::with_capacity(local.len());
+
+    for ts in local {
+        let candidates = [*ts - Duration::hours(2), *ts - Duration::hours(1)];
+        let mut valid: Vec<NaiveDateTime> = candidates
+            .iter()
+            .filter(|utc| to_local(**utc) == *ts)
+            .cloned()
+            .collect();
+        if valid.is_empty() {
+            // the skipped hour in march, keep both readings and let the series decide
+            valid = candidates.to_vec();
+        }
+
+        let utc = match result.last() {
+            Some(prev) => valid
+                .iter()
+                .filter(|utc| *utc > prev)
+                .min()
+                .or_else(|| valid.iter().max())
+                .cloned(),
+            None => valid.first().cloned(),
+        };
+        result.push(utc.expect("at least one candidate"));
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint_value::timezone::{to_local, to_utc};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_to_local_switches_offset() {
+        let d = NaiveDate::from_ymd_opt(2023, 3, 26).unwrap();
+        assert_eq!(
+            to_local(d.and_hms_opt(0, 45, 0).unwrap()),
+            d.and_hms_opt(1, 45, 0).unwrap()
+        );
+        assert_eq!(
+            to_local(d.and_hms_opt(1, 0, 0).unwrap()),
+            d.and_hms_opt(3, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_to_utc_resolves_repeated_hour() {
+        let d = NaiveDate::from_ymd_opt(2023, 10, 29).unwrap();
+        let local = vec![
+            d.and_hms_opt(2, 30, 0).unwrap(),
+            d.and_hms_opt(2, 45, 0).unwrap(),
+            d.and_hms_opt(2, 0, 0).unwrap(),
+            d.and_hms_opt(2, 15, 0).unwrap(),
+        ];
+        assert_eq!(
+            to_utc(&local),
+            vec![
+                d.and_hms_opt(0, 30, 0).unwrap(),
+                d.and_hms_opt(0, 45, 0).unwrap(),
+                d.and_hms_opt(1, 0, 0).unwrap(),
+                d.and_hms_opt(1, 15, 0).unwrap(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,229 @@
+use crate::meterpoint_value::Data;
+use chrono::{NaiveDateTime, Timelike};
+use std::collections::{BTreeMap, HashSet};
+
+/// Problems of the index, they are the same for every column.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Issues {
+    pub missing_intervals: usize,
+    pub duplicate_timestamps: usize,
+    pub non_monotonic: usize,
+    pub off_grid: usize,
+}
+
+impl Issues {
+    fn counts(&self) -> Vec<(&'static str, usize)> {
+        vec![
+            ("missing_intervals", self.missing_intervals),
+            ("duplicate_timestamps", self.duplicate_timestamps),
+            ("non_monotonic", self.non_monotonic),
+            ("off_grid", self.off_grid),
+        ]
+    }
+}
+
+#[derive(Debug)]
+pub struct Report {
+    index: Issues,
+    /// empty cells of every column
+    empty_values: Vec<(String, usize)>,
+}
+
+impl Report {
+    /// Counts by what they belong to, `index` or a column.
+    fn counts(&self) -> Vec<(String, &'static str, usize)> {
+        let index = self
+            .index
+            .counts()
+            .into_iter()
+            .map(|(name, count)| ("index".to_string(), name, count));
+        let columns = self
+            .empty_values
+            .iter()
+            .map(|(column, count)| (column.clone(), "empty_values", *count));
+        index.chain(columns).collect()
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.counts().iter().all(|(_, _, count)| *count == 0)
+    }
+
+    pub fn write_meta(&self, meta: &mut BTreeMap<String, String>) {
+        for (of, name, count) in self.counts() {
+            meta.insert(format!("validation.{}.{}", of, name), count.to_string());
+        }
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary: Vec<(String, Vec<String>)> = vec![];
+        for (of, name, count) in self.counts() {
+            if count == 0 {
+                continue;
+            }
+            let count = format!("{} {}", count, name);
+            match summary.last_mut() {
+                Some((last, counts)) if *last == of => counts.push(count),
+                _ => summary.push((of, vec![count])),
+            }
+        }
+        summary
+            .into_iter()
+            .map(|(of, counts)| format!("{}: {}", of, counts.join(", ")))
+            .collect::<Vec<String>>()
+            .join("; ")
+    }
+}
+
+fn is_on_grid(ts: &NaiveDateTime) -> bool {
+    ts.minute().is_multiple_of(15) && ts.second() == 0 && ts.nanosecond() == 0
+}
+
+fn index_issues(data: &Data) -> Issues {
+    let instants = data.instants();
+    let mut issues = Issues {
+        off_grid: data.index.iter().filter(|ts| !is_on_grid(ts)).count(),
+        ..Issues::default()
+    };
+
+    let mut seen: HashSet<NaiveDateTime> = HashSet::new();
+    for (i, ts) in instants.iter().enumerate() {
+        if !seen.insert(*ts) {
+            issues.duplicate_timestamps += 1;
+        }
+        if i > 0 && *ts < instants[i - 1] {
+            issues.non_monotonic += 1;
+        }
+    }
+
+    let mut unique: Vec<NaiveDateTime> = seen.into_iter().collect();
+    unique.sort();
+    let interval = Data::interval().num_seconds();
+    for pair in unique.windows(2) {
+        let slots = (pair[1] - pair[0]).num_seconds() / interval;
+        if slots > 1 {
+            issues.missing_intervals += (slots - 1) as usize;
+        }
+    }
+
+    issues
+}
+
+/// Checks once that the index forms a regular 15 minute grid and counts empty cells per
+/// meterpoint.
+pub fn validate(data: &Data) -> Report {
+    let empty_values = (0..data.columns.len())
+        .map(|c| {
+            let empty = data
+                .data
+                .iter()
+                .filter(|row| row.get(c).is_none_or(|v| v.is_none()))
+                .count();
+            (data.label(c), empty)
+        })
+        .collect();
+
+    Report {
+        index: index_issues(data),
+        empty_values,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::register::Register;
+    use crate::meterpoint_value::validate::{validate, Issues};
+    use crate::meterpoint_value::{Data, Stamp};
+    use chrono::{NaiveDate, NaiveDateTime};
+    use std::collections::BTreeMap;
+
+    const AT1: &str = "AT0020000000000000000000000000001";
+
+    fn data(index: Vec<NaiveDateTime>, stamp: Stamp) -> Data {
+        let data = index.iter().map(|_| vec![Some(1.0), None]).collect();
+        Data {
+            columns: vec![
+                Meterpoint::parse(AT1).unwrap(),
+                Meterpoint::parse(AT1).unwrap(),
+            ],
+            registers: vec![Register::ActiveImport, Register::ActiveExport],
+            index,
+            data,
+            stamp,
+            quality: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_reports_index_and_value_issues() {
+        let d = NaiveDate::from_ymd_opt(2023, 1, 5).unwrap();
+        let report = validate(&data(
+            vec![
+                d.and_hms_opt(0, 0, 0).unwrap(),
+                d.and_hms_opt(0, 15, 0).unwrap(),
+                d.and_hms_opt(1, 0, 0).unwrap(),
+                d.and_hms_opt(0, 45, 0).unwrap(),
+                d.and_hms_opt(1, 0, 0).unwrap(),
+                d.and_hms_opt(1, 20, 0).unwrap(),
+            ],
+            Stamp::Start,
+        ));
+
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.index,
+            Issues {
+                missing_intervals: 1,
+                duplicate_timestamps: 1,
+                non_monotonic: 1,
+                off_grid: 1,
+            }
+        );
+        assert_eq!(
+            report.empty_values,
+            vec![(format!("{}:1.8.0", AT1), 0), (format!("{}:2.8.0", AT1), 6)]
+        );
+        assert_eq!(
+            report.summary(),
+            format!(
+                "index: 1 missing_intervals, 1 duplicate_timestamps, 1 non_monotonic, 1 off_grid; \
+                 {}:2.8.0: 6 empty_values",
+                AT1
+            )
+        );
+
+        let mut meta: BTreeMap<String, String> = BTreeMap::new();
+        report.write_meta(&mut meta);
+        assert_eq!(
+            meta.get("validation.index.missing_intervals"),
+            Some(&"1".to_string())
+        );
+        assert_eq!(
+            meta.get(&format!("validation.{}:2.8.0.empty_values", AT1)),
+            Some(&"6".to_string())
+        );
+        assert!(!meta.contains_key(&format!("validation.{}:1.8.0.missing_intervals", AT1)));
+    }
+
+    #[test]
+    fn test_validate_accepts_dst_changes() {
+        let spring = NaiveDate::from_ymd_opt(2023, 3, 26).unwrap();
+        let autumn = NaiveDate::from_ymd_opt(2023, 10, 29).unwrap();
+        let mut index = vec![
+            spring.and_hms_opt(1, 45, 0).unwrap(),
+            spring.and_hms_opt(2, 0, 0).unwrap(),
+            spring.and_hms_opt(3, 15, 0).unwrap(),
+        ];
+        let report = validate(&data(index.clone(), Stamp::End));
+        assert_eq!(report.index, Issues::default());
+
+        index = vec![
+            autumn.and_hms_opt(2, 45, 0).unwrap(),
+            autumn.and_hms_opt(3, 0, 0).unwrap(),
+            autumn.and_hms_opt(2, 15, 0).unwrap(),
+            autumn.and_hms_opt(2, 30, 0).unwrap(),
+        ];
+        let report = validate(&data(index, Stamp::End));
+        assert_eq!(report.index, Issues::default());
+    }
+}
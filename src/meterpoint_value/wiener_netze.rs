@@ -1,49 +1,52 @@
-use crate::meterpoint_value::Data;
+use crate::excel;
+use crate::meterpoint_value::register::{parse_headers, Register};
+use crate::meterpoint_value::{Data, Stamp};
 use crate::ImportError;
 use calamine::{DataType, Range};
 use chrono::SubsecRound;
 
 pub fn run(sheet: Range<DataType>) -> Result<Data, ImportError> {
-    let headers: Vec<String> = sheet
-        .rows()
-        .nth(6)
-        .unwrap()
-        .iter()
-        .skip(2)
-        .map(|header_cell| {
-            return match header_cell.get_string() {
-                None => String::from(""),
-                Some(v) => v.to_string(),
-            };
+    let (headers, _) = parse_headers(excel::cells_from(excel::row(&sheet, 6)?, 2), 6)?;
+
+    // row 13 names the measured quantity, e.g. Wirkverbrauch_kWh
+    let quantity = sheet
+        .get_value((13, 1))
+        .and_then(|v| Register::detect(&v.to_string()));
+    let registers: Vec<Register> = (0..headers.len())
+        .map(|c| {
+            sheet
+                .get_value((13, c as u32 + 2))
+                .and_then(|v| Register::detect(&v.to_string()))
+                .or(quantity)
+                .unwrap_or(Register::ActiveImport)
         })
         .collect();
 
     let header_cnt = headers.len();
     let mut r = Data {
         columns: headers,
+        registers,
         index: vec![],
         data: vec![],
+        stamp: Stamp::End,
+        quality: None,
     };
 
     for (i, row) in sheet.rows().enumerate().skip(14) {
-        let date = row[0]
-            .as_datetime()
-            .ok_or_else(|| {
-                ImportError::ValueError(
-                    i,
-                    "Timestamp".to_string(),
-                    "could not parse datetime".to_string(),
-                )
-            })?
-            .round_subsecs(0);
+        let date = excel::required(
+            i,
+            "Timestamp",
+            excel::cell(row, 0),
+            "datetime",
+            excel::as_datetime,
+        )?
+        .round_subsecs(0);
 
         let values = row
             .iter()
             .skip(2)
             .take(header_cnt)
-            .map(|v| {
-                return v.get_float().map(|f| f / 4.0);
-            })
+            .map(|v| excel::as_float(v).map(|f| f / 4.0))
             .collect();
 
         r.index.push(date);
@@ -55,12 +58,16 @@ pub fn run(sheet: Range<DataType>) -> Result<Data, ImportError> {
 
 #[cfg(test)]
 mod tests {
-    use crate::meterpoint_value::run;
+    use crate::meterpoint::Meterpoint;
+    use crate::meterpoint_value::{run, selection};
     use chrono::NaiveDate;
 
     #[test]
     fn test_parse_wiener_netze_is_successful() {
-        let result = run("var/meterpoint_value_wiener_netze.xlsx".to_string());
+        let result = run(
+            "var/meterpoint_value_wiener_netze.xlsx".to_string(),
+            &selection(),
+        );
         assert!(result.is_ok());
 
         let data = result.unwrap();
@@ -68,8 +75,8 @@ mod tests {
         assert_eq!(
             data.columns,
             vec![
-                String::from("AT0010000000000000001000001234567"),
-                String::from("AT0010000000000000001000001234568"),
+                Meterpoint::parse("AT0010000000000000001000001234567").unwrap(),
+                Meterpoint::parse("AT0010000000000000001000001234568").unwrap(),
             ]
         );
 
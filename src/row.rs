@@ -0,0 +1,402 @@
+use crate::decimal::Decimal;
+use crate::excel;
+use crate::meterpoint::Meterpoint;
+use crate::{ImportError, Reason};
+use calamine::DataType;
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use regex::{Regex, RegexBuilder};
+use std::collections::HashMap;
+
+pub use imports_derive::ExcelRow;
+
+/// Header of a column and other spellings of it, compared case insensitive.
+pub struct Header {
+    pub name: &'static str,
+    pub aliases: &'static [&'static str],
+    /// regular expressions for headers that vary, e.g. with a year
+    pub patterns: &'static [&'static str],
+}
+
+impl Header {
+    fn names(&self) -> impl Iterator<Item = String> {
+        std::iter::once(self.name)
+            .chain(self.aliases.iter().copied())
+            .map(|name| name.trim().to_lowercase())
+    }
+
+    fn patterns(&self) -> Result<Vec<Regex>, ImportError> {
+        self.patterns
+            .iter()
+            .map(|pattern| {
+                RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .map_err(|e| {
+                        ImportError::Error(format!(
+                            "Invalid pattern {} of {}: {}",
+                            pattern, self.name, e
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+/// No header name or alias may belong to two columns.
+pub fn check_headers(headers: &[Header]) -> Result<(), String> {
+    let mut seen: HashMap<String, &str> = HashMap::new();
+    for header in headers {
+        for name in header.names() {
+            match seen.insert(name.clone(), header.name) {
+                Some(other) if other != header.name => {
+                    return Err(format!(
+                        "Header {} is claimed by the columns {} and {}",
+                        name, other, header.name
+                    ))
+                }
+                _ => {}
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A row of an import, implemented with `#[derive(ExcelRow)]`.
+pub trait ExcelRow: Sized {
+    /// Columns in the order of the fields that are read from the sheet.
+    const HEADERS: &'static [Header];
+
+    /// `columns` are the indices of [`Self::HEADERS`] in `row`, see [`column_map`].
+    fn from_cells(
+        row: &[DataType],
+        columns: &[usize],
+        row_number: usize,
+    ) -> Result<Self, ImportError>;
+}
+
+/// Index of every column of `R` in the header row. Headers that belong to no column are an
+/// error, so are columns without a header.
+pub fn column_map<R: ExcelRow>(headers: &[DataType]) -> Result<Vec<usize>, ImportError> {
+    check_headers(R::HEADERS).map_err(ImportError::Error)?;
+    let names: Vec<Vec<String>> = R::HEADERS.iter().map(|h| h.names().collect()).collect();
+    let patterns = R::HEADERS
+        .iter()
+        .map(Header::patterns)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut map: Vec<Option<usize>> = vec![None; R::HEADERS.len()];
+    for (i, header) in headers.iter().enumerate() {
+        let header = header.get_string().unwrap_or_default();
+        let normalised = header.trim().to_lowercase();
+        let columns: Vec<usize> = (0..R::HEADERS.len())
+            .filter(|&c| {
+                names[c].contains(&normalised)
+                    || patterns[c].iter().any(|p| p.is_match(header.trim()))
+            })
+            .collect();
+
+        match columns[..] {
+            [column] => map[column] = Some(i),
+            [] => return Err(ImportError::UnknownHeader(header.to_string())),
+            _ => {
+                return Err(ImportError::Error(format!(
+                    "Header {} matches the columns {}",
+                    header,
+                    columns
+                        .iter()
+                        .map(|&c| R::HEADERS[c].name)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )))
+            }
+        }
+    }
+
+    map.iter()
+        .zip(R::HEADERS)
+        .map(|(index, header)| {
+            index.ok_or_else(|| ImportError::MissingHeader(header.name.to_string()))
+        })
+        .collect()
+}
+
+/// Position of the column `name` in [`ExcelRow::HEADERS`], for checks that look at the cells of
+/// a row before it is read.
+pub fn position<R: ExcelRow>(name: &str) -> usize {
+    R::HEADERS
+        .iter()
+        .position(|header| header.name == name)
+        .unwrap_or_else(|| panic!("{} is no column of the row", name))
+}
+
+/// A value read from a single cell.
+pub trait FromCell: Sized {
+    /// `None` for empty cells, the reason why the cell could not be read otherwise.
+    fn from_cell(cell: &DataType) -> Result<Option<Self>, Reason>;
+
+    /// Value of an empty cell in a column that is not optional, an error if `None`.
+    fn empty() -> Option<Self> {
+        None
+    }
+}
+
+pub fn required<T: FromCell>(
+    row_number: usize,
+    column: &str,
+    cell: &DataType,
+) -> Result<T, ImportError> {
+    match T::from_cell(cell) {
+        Ok(Some(value)) => Ok(value),
+        Ok(None) => T::empty().ok_or_else(|| excel::empty(row_number, column)),
+        Err(reason) => Err(excel::value_error(row_number, column, cell, reason)),
+    }
+}
+
+pub fn optional<T: FromCell>(
+    row_number: usize,
+    column: &str,
+    cell: &DataType,
+) -> Result<Option<T>, ImportError> {
+    T::from_cell(cell).map_err(|reason| excel::value_error(row_number, column, cell, reason))
+}
+
+/// Flag columns that are set by one of the values `yes`, like `E` in "SEPA hinterlegt E=JA".
+/// Values that read as no, e.g. `N` or `0`, are `false` and empty cells are `None`, anything else
+/// is reported as unknown value.
+pub fn flag(
+    row_number: usize,
+    column: &str,
+    cell: &DataType,
+    yes: &[&str],
+) -> Result<Option<bool>, ImportError> {
+    let value = cell.to_string().trim().to_lowercase();
+    if value.is_empty() {
+        return Ok(None);
+    }
+    if yes.iter().any(|y| y.trim().to_lowercase() == value) {
+        return Ok(Some(true));
+    }
+    match excel::as_bool(cell) {
+        Some(false) => Ok(Some(false)),
+        _ => Err(excel::value_error(
+            row_number,
+            column,
+            cell,
+            Reason::UnknownEnumValue,
+        )),
+    }
+}
+
+fn read<T>(
+    cell: &DataType,
+    expected: &'static str,
+    read: fn(&DataType) -> Option<T>,
+) -> Result<Option<T>, Reason> {
+    match read(cell) {
+        Some(value) => Ok(Some(value)),
+        None => match excel::reason(cell, expected) {
+            Reason::Empty => Ok(None),
+            reason => Err(reason),
+        },
+    }
+}
+
+impl FromCell for String {
+    fn from_cell(cell: &DataType) -> Result<Option<String>, Reason> {
+        let value = cell.to_string().trim().to_string();
+        Ok(Some(value).filter(|v| !v.is_empty()))
+    }
+
+    /// text columns have always accepted empty cells
+    fn empty() -> Option<String> {
+        Some(String::new())
+    }
+}
+
+impl FromCell for f64 {
+    fn from_cell(cell: &DataType) -> Result<Option<f64>, Reason> {
+        read(cell, "number", excel::as_float)
+    }
+}
+
+impl FromCell for i64 {
+    fn from_cell(cell: &DataType) -> Result<Option<i64>, Reason> {
+        read(cell, "integer", excel::as_int)
+    }
+}
+
+impl FromCell for bool {
+    fn from_cell(cell: &DataType) -> Result<Option<bool>, Reason> {
+        read(cell, "boolean", excel::as_bool)
+    }
+
+    /// flags are mostly marked with `X` and left empty otherwise
+    fn empty() -> Option<bool> {
+        Some(false)
+    }
+}
+
+impl FromCell for NaiveDate {
+    fn from_cell(cell: &DataType) -> Result<Option<NaiveDate>, Reason> {
+        read(cell, "date", excel::as_date)
+    }
+}
+
+impl FromCell for NaiveTime {
+    fn from_cell(cell: &DataType) -> Result<Option<NaiveTime>, Reason> {
+        read(cell, "time", excel::as_time)
+    }
+}
+
+impl FromCell for NaiveDateTime {
+    fn from_cell(cell: &DataType) -> Result<Option<NaiveDateTime>, Reason> {
+        read(cell, "datetime", excel::as_datetime)
+    }
+}
+
+impl FromCell for Decimal {
+    fn from_cell(cell: &DataType) -> Result<Option<Decimal>, Reason> {
+        Decimal::from_excel_value(cell).map_err(Reason::ParseFailure)
+    }
+}
+
+impl FromCell for Meterpoint {
+    fn from_cell(cell: &DataType) -> Result<Option<Meterpoint>, Reason> {
+        Meterpoint::from_excel_value(cell.to_string()).map_err(Reason::ParseFailure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::row::{check_headers, column_map, ExcelRow, Header};
+    use crate::{ImportError, Reason};
+    use calamine::DataType;
+    use chrono::NaiveDate;
+
+    #[derive(Debug, ExcelRow)]
+    struct Row {
+        #[excel(header = "Kunde")]
+        customer: String,
+        #[excel(header = "Datum", alias = "Buch.dat.", alias = "Buchungsdatum")]
+        date: NaiveDate,
+        #[excel(header = "Menge", pattern = r"^menge \d{4}$")]
+        amount: Option<f64>,
+        #[excel(skip)]
+        note: Option<String>,
+    }
+
+    fn cells(values: &[&str]) -> Vec<DataType> {
+        values
+            .iter()
+            .map(|v| match v.is_empty() {
+                true => DataType::Empty,
+                false => DataType::String(v.to_string()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_column_map() {
+        let columns = column_map::<Row>(&cells(&["Menge", " BUCHUNGSDATUM ", "kunde"])).unwrap();
+        assert_eq!(columns, vec![2, 1, 0]);
+
+        let columns = column_map::<Row>(&cells(&["Kunde", "Menge 2024", "Datum"])).unwrap();
+        assert_eq!(columns, vec![0, 2, 1]);
+
+        assert!(matches!(
+            column_map::<Row>(&cells(&["Kunde", "Datum"])),
+            Err(ImportError::MissingHeader(ref h)) if h == "Menge"
+        ));
+        assert!(matches!(
+            column_map::<Row>(&cells(&["Kunde", "Datum", "Menge", "Preis"])),
+            Err(ImportError::UnknownHeader(ref h)) if h == "Preis"
+        ));
+    }
+
+    #[test]
+    fn test_from_cells() {
+        let columns = [0, 1, 2];
+        let row = Row::from_cells(&cells(&["", "31.12.2023", ""]), &columns, 4).unwrap();
+        assert_eq!(row.customer, "");
+        assert_eq!(row.date, NaiveDate::from_ymd_opt(2023, 12, 31).unwrap());
+        assert_eq!(row.amount, None);
+        assert_eq!(row.note, None);
+
+        let row = Row::from_cells(&cells(&["1", "31.12.2023", "1.234,5"]), &columns, 4).unwrap();
+        assert_eq!(row.amount, Some(1234.5));
+
+        assert!(matches!(
+            Row::from_cells(&cells(&["1", "", ""]), &columns, 4),
+            Err(ImportError::ValueError(4, ref column, _, Reason::Empty)) if column == "Datum"
+        ));
+        assert!(matches!(
+            Row::from_cells(&cells(&["1", "31.12.2023", "viel"]), &columns, 4),
+            Err(ImportError::ValueError(4, _, ref value, Reason::ParseFailure(_))) if value == "viel"
+        ));
+    }
+
+    #[derive(Debug, ExcelRow)]
+    struct Flags {
+        #[excel(header = "SEPA hinterlegt E=JA", yes = "E")]
+        sepa: bool,
+        #[excel(header = "SEPA gesperrt")]
+        blocked: bool,
+        #[excel(header = "Anzahl")]
+        count: Option<i64>,
+    }
+
+    #[test]
+    fn test_flags() {
+        let columns = [0, 1, 2];
+        let row = Flags::from_cells(&cells(&["e", "X", "3"]), &columns, 2).unwrap();
+        assert!(row.sepa && row.blocked);
+        assert_eq!(row.count, Some(3));
+
+        let row = Flags::from_cells(&cells(&["N", "", ""]), &columns, 2).unwrap();
+        assert!(!row.sepa && !row.blocked);
+        assert_eq!(row.count, None);
+
+        let row = Flags::from_cells(&cells(&["", "nein", ""]), &columns, 2).unwrap();
+        assert!(!row.sepa && !row.blocked);
+
+        // values that are neither the yes value nor a no are typos, not unset flags
+        for typo in ["ja", "X", "EE"] {
+            assert!(matches!(
+                Flags::from_cells(&cells(&[typo, "", ""]), &columns, 2),
+                Err(ImportError::ValueError(2, ref column, ref value, Reason::UnknownEnumValue))
+                    if column == "SEPA hinterlegt E=JA" && value == typo
+            ));
+        }
+
+        assert!(matches!(
+            Flags::from_cells(&cells(&["", "vielleicht", ""]), &columns, 2),
+            Err(ImportError::ValueError(2, ref column, _, _)) if column == "SEPA gesperrt"
+        ));
+    }
+
+    #[test]
+    fn test_check_headers() {
+        let header = |name, aliases| Header {
+            name,
+            aliases,
+            patterns: &[],
+        };
+        assert!(check_headers(&[header("Zählpunkt", &["ZP"]), header("Name", &[])]).is_ok());
+        assert_eq!(
+            check_headers(&[header("Zählpunkt", &["ZP"]), header("ZP ", &[])]),
+            Err("Header zp is claimed by the columns Zählpunkt and ZP ".to_string())
+        );
+    }
+
+    #[test]
+    fn test_importer_headers_are_unique() {
+        use crate::{commission, contact_attempt, customer_tag, datentraeger, kam, sap};
+
+        assert_eq!(check_headers(commission::Row::HEADERS), Ok(()));
+        assert_eq!(check_headers(contact_attempt::Row::HEADERS), Ok(()));
+        assert_eq!(check_headers(customer_tag::Row::HEADERS), Ok(()));
+        assert_eq!(check_headers(datentraeger::Row::HEADERS), Ok(()));
+        assert_eq!(check_headers(kam::Row::HEADERS), Ok(()));
+        assert_eq!(check_headers(sap::Row::HEADERS), Ok(()));
+    }
+}
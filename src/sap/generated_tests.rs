@@ -0,0 +1,152 @@
+//! Generated by create-import from the definition of the importer, changes are overwritten.
+
+use super::{from_range, Row};
+use crate::row::column_map;
+use crate::ImportError;
+use calamine::{DataType, Range};
+
+/// Names and aliases of every column in the order of [`Row`].
+const NAMES: &[&[&str]] = &[
+    &["BA"],
+    &["Abrmenge"],
+    &["Vertrag"],
+    &["Vertragskont"],
+    &["TWährg"],
+    &["Sp"],
+    &["Buch.dat."],
+    &["Druckbeleg"],
+    &["BArt"],
+    &["ZP"],
+    &["Nettobetrag"],
+    &["Preisbetrag"],
+    &["AblEinh."],
+    &["Geschäftspartner"],
+    &["Tariftyp"],
+    &["Gültig ab"],
+    &["Gültig bis"],
+];
+
+fn text(value: &str) -> DataType {
+    DataType::String(value.to_string())
+}
+
+fn headers() -> Vec<DataType> {
+    NAMES.iter().map(|names| text(names[0])).collect()
+}
+
+#[test]
+fn test_every_name_and_alias_is_accepted() {
+    for (column, names) in NAMES.iter().enumerate() {
+        for name in names.iter() {
+            let mut headers = headers();
+            headers[column] = text(name);
+            let columns = column_map::<Row>(&headers).unwrap_or_else(|e| panic!("{}: {}", name, e));
+            assert_eq!(columns[column], column, "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_unknown_header_is_rejected() {
+    let mut headers = headers();
+    headers.push(text("Unbekannte Spalte"));
+    assert!(matches!(
+        column_map::<Row>(&headers),
+        Err(ImportError::UnknownHeader(ref header)) if header == "Unbekannte Spalte"
+    ));
+}
+
+#[test]
+fn test_fixture_row() {
+    let cells = [
+        text("ba"),
+        DataType::Float(1.5),
+        text("contract"),
+        text("contract_account"),
+        text("currency"),
+        text("energy_type"),
+        DataType::DateTime(45291.0),
+        text("invoice_id"),
+        text("line_id"),
+        text("AT0020000000000000000000000012345"),
+        DataType::Float(13.35092),
+        DataType::Float(13.35092),
+        text("read_unit"),
+        text("supplier_customer_id"),
+        text("tariff"),
+        DataType::DateTime(45291.0),
+        DataType::DateTime(45291.0),
+    ];
+    let last = (cells.len() - 1) as u32;
+    let mut sheet = Range::new((0, 0), (1, last));
+    for (i, header) in headers().into_iter().enumerate() {
+        sheet.set_value((0, i as u32), header);
+    }
+    for (i, cell) in cells.into_iter().enumerate() {
+        sheet.set_value((1, i as u32), cell);
+    }
+
+    let groups = from_range(&sheet).unwrap();
+    let rows = groups.get("invoice_id").unwrap();
+    assert_eq!(rows.len(), 1);
+    let row = &rows[0];
+    assert_eq!(serde_json::json!(row.ba), serde_json::json!("ba"));
+    assert_eq!(
+        serde_json::json!(row.billing_amount),
+        serde_json::json!(1.5)
+    );
+    assert_eq!(
+        serde_json::json!(row.contract),
+        serde_json::json!("contract")
+    );
+    assert_eq!(
+        serde_json::json!(row.contract_account),
+        serde_json::json!("contract_account")
+    );
+    assert_eq!(
+        serde_json::json!(row.currency),
+        serde_json::json!("currency")
+    );
+    assert_eq!(
+        serde_json::json!(row.energy_type),
+        serde_json::json!("energy_type")
+    );
+    assert_eq!(
+        serde_json::json!(row.entry_date),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.invoice_id),
+        serde_json::json!("invoice_id")
+    );
+    assert_eq!(serde_json::json!(row.line_id), serde_json::json!("line_id"));
+    assert_eq!(
+        serde_json::json!(row.meterpoint),
+        serde_json::json!("AT0020000000000000000000000012345")
+    );
+    assert_eq!(
+        serde_json::json!(row.net_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.price_amount),
+        serde_json::json!("13.35092")
+    );
+    assert_eq!(
+        serde_json::json!(row.read_unit),
+        serde_json::json!("read_unit")
+    );
+    assert_eq!(
+        serde_json::json!(row.supplier_customer_id),
+        serde_json::json!("supplier_customer_id")
+    );
+    assert_eq!(serde_json::json!(row.tariff), serde_json::json!("tariff"));
+    assert_eq!(
+        serde_json::json!(row.valid_from),
+        serde_json::json!("2023-12-31")
+    );
+    assert_eq!(
+        serde_json::json!(row.valid_to),
+        serde_json::json!("2023-12-31")
+    );
+}
@@ -1,8 +1,8 @@
-use crate::sap::run;
+use crate::sap::{run, selection};
 
 #[test]
 fn test_get_column_map_success_with_ordered_columns() {
-    let result = run("var/sap.xlsx");
+    let result = run("var/sap.xlsx", &selection());
     assert!(result.is_ok());
 
     let result = result.unwrap();
@@ -0,0 +1,211 @@
+use crate::delimited;
+use crate::ImportError;
+use calamine::{open_workbook_auto, DataType, Range, Reader};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use regex::Regex;
+
+/// File formats the importers read their cells from.
+#[derive(Debug, PartialEq)]
+pub enum Format {
+    /// xlsx, xlsm, xlsb and xls
+    Workbook,
+    OpenDocument,
+    /// csv, txt and tsv exports, see [`delimited`]
+    Delimited,
+}
+
+impl Format {
+    pub fn detect<P: AsRef<std::path::Path>>(path: P) -> Format {
+        if delimited::is_delimited(&path) {
+            return Format::Delimited;
+        }
+        let extension = path
+            .as_ref()
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase());
+        match extension.as_deref() {
+            Some("ods") => Format::OpenDocument,
+            _ => Format::Workbook,
+        }
+    }
+}
+
+/// Sheet of a workbook an importer reads its rows from.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum Sheet {
+    #[default]
+    First,
+    Name(String),
+    Pattern(String),
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Selection {
+    pub sheet: Sheet,
+    /// read every matching sheet instead of only the first one
+    pub all: bool,
+}
+
+impl Selection {
+    /// Every sheet of the workbook is read, not only those with a name or pattern.
+    pub fn every_sheet(&self) -> bool {
+        self.all && self.sheet == Sheet::First
+    }
+
+    fn select(&self, names: &[String]) -> Result<Vec<String>, ImportError> {
+        let mut selected: Vec<String> = match &self.sheet {
+            Sheet::First => names.to_vec(),
+            Sheet::Name(name) => names.iter().filter(|n| *n == name).cloned().collect(),
+            Sheet::Pattern(pattern) => {
+                let re = Regex::new(pattern).map_err(|e| {
+                    ImportError::Error(format!("Invalid sheet pattern {}: {}", pattern, e))
+                })?;
+                names.iter().filter(|n| re.is_match(n)).cloned().collect()
+            }
+        };
+        if !self.all {
+            selected.truncate(1);
+        }
+
+        if selected.is_empty() {
+            return Err(match &self.sheet {
+                Sheet::First => ImportError::Error("Could not find any sheet in excel".to_string()),
+                Sheet::Name(name) | Sheet::Pattern(name) => {
+                    ImportError::SheetNotFound(name.to_string())
+                }
+            });
+        }
+        Ok(selected)
+    }
+}
+
+/// Reads the selected sheets with their names. Whatever the format, dates and times end up as
+/// `DataType::DateTime` and numbers as `DataType::Float`, so the importers produce the same rows.
+/// Delimited files have a single sheet named after the file.
+pub fn sheets<P: AsRef<std::path::Path>>(
+    path: P,
+    selection: &Selection,
+) -> Result<Vec<(String, Range<DataType>)>, ImportError> {
+    let format = Format::detect(&path);
+    if format == Format::Delimited {
+        let name = path
+            .as_ref()
+            .file_name()
+            .map_or(String::new(), |n| n.to_string_lossy().to_string());
+        return Ok(vec![(name, delimited::read(path)?)]);
+    }
+
+    let mut workbook = open_workbook_auto(path)?;
+    let mut sheets = vec![];
+    for name in selection.select(workbook.sheet_names())? {
+        let sheet = workbook
+            .worksheet_range(&name)
+            .ok_or_else(|| ImportError::SheetNotFound(name.clone()))?
+            .map_err(|e| ImportError::Sheet(name.clone(), Box::new(e.into())))?;
+        let sheet = match format {
+            Format::OpenDocument => normalise_ods(sheet),
+            _ => sheet,
+        };
+        sheets.push((name, sheet));
+    }
+    Ok(sheets)
+}
+
+/// calamine returns ods dates as ISO strings (`2023-01-31T08:30:00`) and times as ISO durations
+/// (`PT08H30M00S`).
+fn ods_datetime(value: &str) -> Option<NaiveDateTime> {
+    if let Ok(datetime) = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M:%S%.f") {
+        return Some(datetime);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return date.and_hms_opt(0, 0, 0);
+    }
+
+    let duration = Regex::new(r"^PT(\d{1,2})H(\d{1,2})M(\d{1,2})(?:\.\d+)?S$").unwrap();
+    let captures = duration.captures(value)?;
+    let time = NaiveTime::from_hms_opt(
+        captures[1].parse().ok()?,
+        captures[2].parse().ok()?,
+        captures[3].parse().ok()?,
+    )?;
+    NaiveDate::from_ymd_opt(1899, 12, 30).map(|epoch| epoch.and_time(time))
+}
+
+fn normalise_ods(sheet: Range<DataType>) -> Range<DataType> {
+    let (row_offset, column_offset) = match sheet.start() {
+        Some(start) => start,
+        None => return sheet,
+    };
+
+    let mut normalised = sheet.clone();
+    for (row, column, value) in sheet.used_cells() {
+        if let DataType::String(value) = value {
+            if let Some(datetime) = ods_datetime(value) {
+                normalised.set_value(
+                    (row_offset + row as u32, column_offset + column as u32),
+                    DataType::DateTime(delimited::serial(datetime)),
+                );
+            }
+        }
+    }
+    normalised
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::source::{ods_datetime, Format, Selection, Sheet};
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_detect_format() {
+        assert_eq!(Format::detect("var/sap.XLSX"), Format::Workbook);
+        assert_eq!(Format::detect("var/sap.xls"), Format::Workbook);
+        assert_eq!(Format::detect("var/sap.ods"), Format::OpenDocument);
+        assert_eq!(Format::detect("var/sap.TXT"), Format::Delimited);
+    }
+
+    #[test]
+    fn test_ods_datetime() {
+        let d = NaiveDate::from_ymd_opt(2023, 1, 31).unwrap();
+        assert_eq!(ods_datetime("2023-01-31"), d.and_hms_opt(0, 0, 0));
+        assert_eq!(ods_datetime("2023-01-31T08:30:00"), d.and_hms_opt(8, 30, 0));
+        assert_eq!(
+            ods_datetime("PT08H30M00S"),
+            NaiveDate::from_ymd_opt(1899, 12, 30)
+                .unwrap()
+                .and_hms_opt(8, 30, 0)
+        );
+        assert_eq!(ods_datetime("2023"), None);
+    }
+
+    #[test]
+    fn test_select_sheets() {
+        let names: Vec<String> = ["Deckblatt", "Tags 2023", "Tags 2024"]
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+
+        let select = |sheet: Sheet, all: bool| Selection { sheet, all }.select(&names);
+
+        assert_eq!(select(Sheet::First, false).unwrap(), vec!["Deckblatt"]);
+        assert_eq!(
+            select(Sheet::Name("Tags 2024".to_string()), false).unwrap(),
+            vec!["Tags 2024"]
+        );
+        assert_eq!(
+            select(Sheet::Pattern("^Tags".to_string()), true).unwrap(),
+            vec!["Tags 2023", "Tags 2024"]
+        );
+        assert_eq!(
+            select(Sheet::Pattern("^Tags".to_string()), false).unwrap(),
+            vec!["Tags 2023"]
+        );
+        assert!(select(Sheet::Name("Tags".to_string()), false).is_err());
+
+        let every_sheet = |sheet: Sheet, all: bool| Selection { sheet, all }.every_sheet();
+        assert!(every_sheet(Sheet::First, true));
+        assert!(!every_sheet(Sheet::First, false));
+        assert!(!every_sheet(Sheet::Pattern("^Tags".to_string()), true));
+        assert!(select(Sheet::Pattern("(".to_string()), false).is_err());
+    }
+}